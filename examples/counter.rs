@@ -0,0 +1,70 @@
+// Created by Sean L. on Jun. 25.
+// Last Updated by Sean L. on Jun. 25.
+// 
+// overture.rs
+// examples/counter.rs
+// 
+// Makabaka1880, 2025. All rights reserved.
+
+// The event loop of `overture::app` reads raw input, so this example only runs on Unix.
+
+#[cfg(unix)]
+use overture::prelude::*;
+#[cfg(unix)]
+use overture::app::{App, Context, Runner};
+#[cfg(unix)]
+use std::time::Duration;
+
+#[cfg(unix)]
+struct Counter {
+    presses: u32,
+}
+
+#[cfg(unix)]
+impl App for Counter {
+    fn event(&mut self, event: Event, ctx: &mut Context) {
+        match event {
+            Event::Key(KeyEvent { code: KeyCode::Char('q'), .. }) => ctx.quit(),
+            Event::Key(_) => {
+                self.presses += 1;
+                ctx.request_redraw();
+            }
+            _ => {}
+        }
+    }
+
+    fn tick(&mut self, ctx: &mut Context) {
+        ctx.request_redraw();
+    }
+
+    fn draw(&mut self, engine: &mut OvertureRenderEngine, ctx: &Context) {
+        let size = ctx.size();
+        engine.clear_scene();
+        engine.load_renderable(
+            primitives::shape::SoftBox::new(DiscreteCoord::ORIGIN, size - DiscreteCoord::new(1, 1))
+                .rasterize()
+                .style(style![ANSISequence::FgCyan]),
+            None,
+        );
+        engine.load_renderable(
+            primitives::Text::new(
+                format!("{} keys pressed, {}s elapsed. Press q to quit.", self.presses, ctx.ticks()),
+                DiscreteCoord::ORIGIN,
+            ),
+            Some(RenderPlacementConfig::CenterStage),
+        );
+    }
+}
+
+#[cfg(unix)]
+fn main() -> std::io::Result<()> {
+    Runner::new()
+        .with_fps(30)
+        .with_tick_rate(Duration::from_secs(1))
+        .run(Counter { presses: 0 })
+}
+
+#[cfg(not(unix))]
+fn main() {
+    eprintln!("The counter example needs a Unix terminal.");
+}
@@ -2,22 +2,30 @@
 // Last Updated by Sean L. on Jun. 24.
 // 
 // overture.rs
-// src/demo.rs
+// examples/demo.rs
 // 
 // Makabaka1880, 2025. All rights reserved.
 
-use crate::prelude::*;
+use overture::prelude::*;
 
 use std::io;
 
-pub fn banner() {
-    let (cols, rows) = (130, 30);
-    let mut engine_instace = OvertureRenderEngine::new(cols as u32, (rows - 3) as u32);
-    let term_dim = DiscreteCoord::new(cols, rows);
+fn main() -> io::Result<()> {
+    banner()
+}
+
+pub fn banner() -> io::Result<()> {
+    #[cfg(unix)]
+    let mut engine_instace = OvertureRenderEngine::fit_terminal()
+        .unwrap_or_else(|_| OvertureRenderEngine::new(130, 30));
+    #[cfg(not(unix))]
+    let mut engine_instace = OvertureRenderEngine::new(130, 30);
+    let term_dim = engine_instace.size();
+    let (cols, rows) = (term_dim.x, term_dim.y);
     let border = primitives::shape::SoftBox::
         new(
             DiscreteCoord::ORIGIN, 
-            DiscreteCoord::new(cols - 1, rows)
+            DiscreteCoord::new(cols - 1, rows - 1)
         );
     let rec = primitives::shape::SoftBox::
         new(
@@ -70,8 +78,14 @@ pub fn banner() {
 
     engine_instace.load_renderable(slogan, Option::None);
     engine_instace.load_renderable(border, Option::None);
-    engine_instace.render((rows) as u16);
-    let mut _s = String::new();
-    let _ = io::stdin().read_line(&mut _s);
+    engine_instace.render((rows) as u16)?;
+
+    // Wait for any key
+    #[cfg(unix)]
+    Input::open()?.read()?;
+    // Raw input is Unix only; wait for Enter instead
+    #[cfg(not(unix))]
+    io::stdin().read_line(&mut String::new())?;
+    Ok(())
 }
 
@@ -0,0 +1,248 @@
+// Created by Sean L. on Jun. 25.
+// Last Updated by Sean L. on Jun. 25.
+//
+// overture.rs
+// src/app.rs
+//
+// Makabaka1880, 2025. All rights reserved.
+
+//! Application Runner
+//!
+//! This module provides [`Runner`], a frame loop driving an [`App`] on top of
+//! [`OvertureRenderEngine`] (Unix only). The runner owns the terminal for the lifetime of the
+//! app: it enters the alternate screen, hides the cursor and enables raw mode (see
+//! [`TerminalSession`] and [`Input`]), and restores everything when the app quits, returns an
+//! error or panics.
+//!
+//! Each iteration of the loop:
+//!
+//! 1. Waits for input until the next tick or frame is due, handing every [`Event`] to
+//!    [`App::event`]. Terminal resizes resize the engine and schedule a redraw.
+//! 2. Calls [`App::tick`] at the configured tick rate.
+//! 3. If the app asked for a redraw, calls [`App::draw`] and renders the frame, at most at the
+//!    target frame rate.
+//!
+//! Nothing is drawn while the state is unchanged, so an idle app does not use any CPU.
+//!
+//! # Example
+//!
+//! A complete application:
+//!
+//! ```rust,no_run
+//! use overture::prelude::*;
+//! use overture::app::{App, Context, Runner};
+//!
+//! struct Counter(u32);
+//!
+//! impl App for Counter {
+//!     fn event(&mut self, event: Event, ctx: &mut Context) {
+//!         match event {
+//!             Event::Key(KeyEvent { code: KeyCode::Char('q'), .. }) => ctx.quit(),
+//!             Event::Key(_) => { self.0 += 1; ctx.request_redraw(); }
+//!             _ => {}
+//!         }
+//!     }
+//!
+//!     fn draw(&mut self, engine: &mut OvertureRenderEngine, _ctx: &Context) {
+//!         engine.clear_scene();
+//!         let label = primitives::Text::new(format!("{} keys pressed, q to quit", self.0), DiscreteCoord::ORIGIN);
+//!         engine.load_renderable(label, Some(RenderPlacementConfig::CenterStage));
+//!     }
+//! }
+//!
+//! fn main() -> std::io::Result<()> {
+//!     Runner::new().with_fps(30).run(Counter(0))
+//! }
+//! ```
+
+use std::io;
+use std::time::{Duration, Instant};
+
+use crate::engine::OvertureRenderEngine;
+use crate::interfaces::geometry::DiscreteCoord;
+use crate::ioopts::input::{Event, Input, KeyCode, KeyEvent, KeyModifiers};
+use crate::ioopts::terminal::{MouseCapture, TerminalSession};
+
+/// An application driven by a [`Runner`].
+///
+/// Only [`draw`](App::draw) is required. State changes made in [`event`](App::event) or
+/// [`tick`](App::tick) are only shown once [`Context::request_redraw`] is called.
+pub trait App {
+    /// Called once before the first frame, with the engine sized to the terminal.
+    fn init(&mut self, _engine: &mut OvertureRenderEngine, _ctx: &mut Context) {}
+
+    /// Handles an input event. Resizes are delivered here too, after the engine was resized.
+    fn event(&mut self, _event: Event, _ctx: &mut Context) {}
+
+    /// Called at the runner's tick rate, if one is configured.
+    fn tick(&mut self, _ctx: &mut Context) {}
+
+    /// Updates the engine's scene to reflect the current state. Called whenever a redraw
+    /// was requested; the runner renders the frame afterwards.
+    ///
+    /// The scene is retained between frames: either clear it with
+    /// [`OvertureRenderEngine::clear_scene`] and rebuild it, or update the objects loaded
+    /// earlier through their [`RenderId`](crate::engine::RenderId)s.
+    fn draw(&mut self, engine: &mut OvertureRenderEngine, ctx: &Context);
+}
+
+/// The state shared between a [`Runner`] and its [`App`].
+#[derive(Debug)]
+pub struct Context {
+    dirty: bool,
+    quit: bool,
+    ticks: u64,
+    size: DiscreteCoord,
+}
+
+impl Context {
+    /// Schedules a call to [`App::draw`] and a render for the next frame.
+    pub fn request_redraw(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Stops the runner after the current iteration. The terminal is then restored.
+    pub fn quit(&mut self) {
+        self.quit = true;
+    }
+
+    /// Returns the number of ticks elapsed since the app started.
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Returns the size of the screen as (columns, rows).
+    pub fn size(&self) -> DiscreteCoord {
+        self.size
+    }
+}
+
+/// Runs an [`App`] in a frame loop, owning terminal setup and teardown.
+///
+/// Configured with `with_*` methods; see the [module documentation](crate::app) for an example.
+///
+/// # Defaults
+///
+/// - 60 frames per second at most.
+/// - No ticks.
+/// - No mouse reporting.
+/// - `Ctrl+C` quits.
+#[derive(Clone, Debug)]
+pub struct Runner {
+    frame_interval: Duration,
+    tick_rate: Option<Duration>,
+    mouse: bool,
+    quit_on_ctrl_c: bool,
+}
+
+impl Default for Runner {
+    fn default() -> Self {
+        Runner::new()
+    }
+}
+
+impl Runner {
+    /// Creates a runner with the default settings.
+    pub fn new() -> Self {
+        Runner {
+            frame_interval: Duration::from_secs(1) / 60,
+            tick_rate: None,
+            mouse: false,
+            quit_on_ctrl_c: true,
+        }
+    }
+
+    /// Sets the maximum number of frames rendered per second. Clamped to at least 1.
+    pub fn with_fps(mut self, fps: u32) -> Self {
+        self.frame_interval = Duration::from_secs(1) / fps.max(1);
+        self
+    }
+
+    /// Calls [`App::tick`] every `rate`.
+    pub fn with_tick_rate(mut self, rate: Duration) -> Self {
+        self.tick_rate = Some(rate);
+        self
+    }
+
+    /// Enables mouse reporting, delivering [`Event::Mouse`] events.
+    pub fn with_mouse(mut self, mouse: bool) -> Self {
+        self.mouse = mouse;
+        self
+    }
+
+    /// Sets whether `Ctrl+C` quits the app. If not, it is delivered to [`App::event`] like any key.
+    pub fn with_quit_on_ctrl_c(mut self, quit: bool) -> Self {
+        self.quit_on_ctrl_c = quit;
+        self
+    }
+
+    /// Takes over the terminal and runs `app` until it quits.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` once the app quit, or the first IO error. The terminal is restored either way.
+    pub fn run<A: App>(&self, mut app: A) -> io::Result<()> {
+        // Dropped in reverse order, restoring the terminal last
+        let _session = TerminalSession::start()?;
+        let mut input = Input::open()?;
+        let _mouse = match self.mouse {
+            true => Some(MouseCapture::enable()?),
+            false => None,
+        };
+
+        let mut engine = OvertureRenderEngine::fit_terminal()?;
+        let mut ctx = Context { dirty: true, quit: false, ticks: 0, size: engine.size() };
+        app.init(&mut engine, &mut ctx);
+
+        let start = Instant::now();
+        let mut next_tick = self.tick_rate.map(|rate| start + rate);
+        let mut next_frame = start;
+
+        while !ctx.quit {
+            let now = Instant::now();
+            if ctx.dirty && now >= next_frame {
+                ctx.dirty = false;
+                app.draw(&mut engine, &ctx);
+                engine.render(engine.size().y as u16)?;
+                next_frame = now + self.frame_interval;
+                continue;
+            }
+            if let (Some(due), Some(rate)) = (next_tick, self.tick_rate) {
+                if now >= due {
+                    ctx.ticks += 1;
+                    app.tick(&mut ctx);
+                    next_tick = Some(due + rate);
+                    continue;
+                }
+            }
+
+            // Sleep on input until something is due
+            let mut wake = next_tick;
+            if ctx.dirty {
+                wake = Some(wake.map_or(next_frame, |t| t.min(next_frame)));
+            }
+            let timeout = wake.map_or(Duration::from_secs(3600), |t| t.saturating_duration_since(now));
+            if let Some(event) = input.poll(timeout)? {
+                self.dispatch(event, &mut app, &mut engine, &mut ctx);
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles the runner's own share of an event, then forwards it to the app.
+    fn dispatch<A: App>(&self, event: Event, app: &mut A, engine: &mut OvertureRenderEngine, ctx: &mut Context) {
+        match event {
+            Event::Key(key) if self.quit_on_ctrl_c && key == KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CTRL) => {
+                ctx.quit();
+                return;
+            }
+            Event::Resize(size) => {
+                engine.resize(size.x, size.y);
+                ctx.size = size;
+                ctx.request_redraw();
+            }
+            _ => {}
+        }
+        app.event(event, ctx);
+    }
+}
@@ -1,5 +1,5 @@
 // Created by Sean L. on Jun. 23.
-// Last Updated by Sean L. on Jun. 23.
+// Last Updated by Sean L. on Jun. 25.
 // 
 // overture.rs
 // src/engine.rs
@@ -11,7 +11,7 @@
 //!
 //! This module provides the [`OvertureRenderEngine`] struct, a terminal-based UI engine for rendering
 //! styled content onto a fixed-width character grid. It supports styled text rendering, element
-//! placement strategies, and renderable object composition via [`RenderableList`].
+//! placement strategies, and renderable object composition via [`RenderableList`](crate::interfaces::containers::RenderableList).
 //!
 //! # Usage
 //!
@@ -20,33 +20,107 @@
 //! 3. Call [`OvertureRenderEngine::render`] with the intended height.
 //! 4. Call [`OvertureRenderEngine::flush`] to finalize display.
 //!
+//! # Incremental Rendering
+//!
+//! The engine remembers the last frame it wrote to the terminal. Subsequent calls to
+//! [`OvertureRenderEngine::render`] only emit the cells that changed since then, positioned
+//! with absolute cursor movements. Use [`OvertureRenderEngine::invalidate`] to force a full repaint.
+//!
+//! # Output Sinks
+//!
+//! Frames are written to the engine's sink, any [`std::io::Write`] implementor. Engines built with
+//! [`OvertureRenderEngine::new`] write to `stdout`; use [`OvertureRenderEngine::with_writer`] to
+//! target a file, socket or pty instead, or [`OvertureRenderEngine::render_to`] to render a single
+//! frame into a borrowed writer such as a `Vec<u8>`. All output methods return [`std::io::Result`].
+//!
+//! # Terminal Size
+//!
+//! [`OvertureRenderEngine::fit_terminal`] builds an engine matching the terminal size, and
+//! [`OvertureRenderEngine::resize`] / [`OvertureRenderEngine::sync_terminal_size`] adapt the buffer
+//! after the terminal was resized.
+//!
+//! # Layers
+//!
+//! Everything loaded into the engine is kept in a [`Layer`]. [`OvertureRenderEngine::load_renderable`]
+//! targets the [`BASE_LAYER`], while [`OvertureRenderEngine::load_renderable_on`] targets any named
+//! layer created with [`OvertureRenderEngine::add_layer`]. Visible layers are composited bottom-up by
+//! `z_index` into the buffer, which is recomposed whenever layers are shown, hidden or reordered.
+//!
+//! # Retained Scene
+//!
+//! Loaded objects are retained as [`SceneObject`]s rather than burnt into the buffer. Each load
+//! returns a [`RenderId`] handle, through which the object can be replaced, restyled, hidden or
+//! removed; only the rows it covers are recomposed, and the next
+//! [`render`](OvertureRenderEngine::render) only emits the cells that changed.
+//!
+//! ```rust
+//! use overture::prelude::*;
+//!
+//! let mut engine = OvertureRenderEngine::new(10, 1);
+//! let clock = engine.load_renderable(primitives::Text::new("12:00", DiscreteCoord::ORIGIN), Some(RenderPlacementConfig::TopRight));
+//! engine.replace_object(clock, primitives::Text::new("12:01", DiscreteCoord::ORIGIN));
+//! assert_eq!(engine.to_plain_string(), "     12:01");
+//!
+//! engine.set_object_visible(clock, false);
+//! assert_eq!(engine.to_plain_string(), "          ");
+//! ```
+//!
+//! # Hit Testing
+//!
+//! Every loaded object gets a [`RenderId`], and the engine records which object drew each cell of
+//! the buffer. [`OvertureRenderEngine::hit_test`] maps a cell, e.g. the position of a mouse event,
+//! back to that object.
+//!
+//! # Snapshots
+//!
+//! The buffer can be inspected without writing anything, via
+//! [`OvertureRenderEngine::to_ansi_string`], [`OvertureRenderEngine::to_plain_string`] and
+//! [`OvertureRenderEngine::cells`]. These are handy for unit tests and golden files.
+//!
 //! # See Also
 //!
 //! - [`Renderable`] trait for compatible objects.
 //! - [`RenderPlacementConfig`] for positioning.
 //! - [`RenderChar`] for styled characters.
 
-use std::io::{Write};
+use std::collections::HashSet;
+use std::io::{self, Write};
 use std::cmp::{max};
+use crate::ioopts::ansi::cursor;
+#[cfg(unix)]
+use crate::ioopts::terminal::terminal_size;
 use crate::interfaces::{
-    rendering::{RenderChar, Renderable},
+    rendering::{RenderChar, Renderable, Transparency},
     pixels::Pixel,
     styling::RenderStyle,
     geometry::{DiscreteCoord, RenderPlacementConfig},
-    containers::{RenderableList}
+    layers::{Layer, Mask, SceneObject},
 };
 
+/// Name of the layer [`OvertureRenderEngine::load_renderable`] loads into. It has a `z_index` of 0.
+pub const BASE_LAYER: &str = "base";
+
+/// Identifies an object loaded into an [`OvertureRenderEngine`], serving as its handle in the scene.
+///
+/// Ids are issued by [`OvertureRenderEngine::load_renderable`] and
+/// [`OvertureRenderEngine::load_renderable_on`], and are unique within an engine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RenderId(u64);
+
 /// A terminal-based UI engine for rendering styled content onto a fixed-width character grid.
 ///
 /// The `OvertureRenderEngine` maintains a 2D buffer of [`RenderChar`]s and renders
 /// them to the terminal using ANSI escape codes. It supports styled text rendering,
-/// element placement strategies, and renderable object composition via [`RenderableList`].
+/// element placement strategies, and renderable object composition via [`RenderableList`](crate::interfaces::containers::RenderableList).
 ///
 /// # Overview
 ///
 /// - `width`: Fixed width of the render area (in characters).
 /// - `buffer`: 2D screen buffer storing what will be printed to terminal.
-/// - `objects`: List of [`Renderable`] elements managed by the engine.
+/// - `front`: The last frame flushed to the terminal, used to diff subsequent frames (private).
+/// - `sink`: The writer receiving rendered frames, `stdout` by default (private).
+/// - `layers`: The layer stack, kept sorted bottom-up by `z_index` (private).
+/// - `owners`: The [`RenderId`] of the object that drew each buffer cell, if any (private).
 ///
 /// # Example
 ///
@@ -56,7 +130,7 @@ use crate::interfaces::{
 /// let mut engine = OvertureRenderEngine::new(40, 20);
 /// let label = primitives::text::Text::new("Hello, world!", DiscreteCoord::ORIGIN);  // implements `Renderable`
 /// engine.load_renderable(label, Some(RenderPlacementConfig::CenterTop));
-/// engine.render(20);
+/// engine.render(20).unwrap();
 /// ```
 ///
 /// # Rendering Flow
@@ -73,8 +147,12 @@ use crate::interfaces::{
 /// - [`RenderChar`] for styled characters.
 pub struct OvertureRenderEngine {
     pub width: u32,
-    pub objects: RenderableList,
     pub buffer: Vec<Vec<RenderChar>>,
+    front: Option<Vec<Vec<RenderChar>>>,
+    sink: Box<dyn Write>,
+    layers: Vec<Layer>,
+    owners: Vec<Vec<Option<RenderId>>>,
+    next_id: u64,
 }
 
 
@@ -82,7 +160,7 @@ impl OvertureRenderEngine {
     /// Creates a new instance of the Overture render engine with the given width and height.
     ///
     /// Initializes the internal screen buffer with blank `RenderChar`s and sets up an empty
-    /// list of objects to render.
+    /// scene. Output is written to `stdout`.
     ///
     /// # Parameters
     ///
@@ -100,11 +178,132 @@ impl OvertureRenderEngine {
     /// let mut engine = OvertureRenderEngine::new(80, 24);
     /// ```
     pub fn new(width: u32, height: u32) -> Self {
+        Self::with_writer(io::stdout(), width, height)
+    }
+
+    /// Creates a new render engine that writes its output to the given writer.
+    ///
+    /// # Parameters
+    ///
+    /// - `writer`: The sink receiving rendered frames (a file, a socket, a pty, ...).
+    /// - `width`: The width (in columns) of the terminal buffer.
+    /// - `height`: The height (in rows) of the terminal buffer.
+    ///
+    /// # Example
+    /// ```
+    /// use overture::prelude::*;
+    /// 
+    /// let log = std::fs::File::create(std::env::temp_dir().join("overture.log")).unwrap();
+    /// let mut engine = OvertureRenderEngine::with_writer(log, 80, 24);
+    /// engine.render(24).unwrap();
+    /// ```
+    pub fn with_writer<W: Write + 'static>(writer: W, width: u32, height: u32) -> Self {
         OvertureRenderEngine {
             width,
-            objects: RenderableList::new(),
             buffer: vec![vec![RenderChar::BLANK_RENDER_CHAR; width as usize]; height as usize],
+            front: None,
+            sink: Box::new(writer),
+            layers: vec![Layer::new(BASE_LAYER, 0)],
+            owners: vec![vec![None; width as usize]; height as usize],
+            next_id: 0,
+        }
+    }
+
+    /// Creates a new render engine filling the terminal, writing to `stdout` (Unix only).
+    ///
+    /// # Returns
+    ///
+    /// The engine, or an error if the terminal size cannot be determined (e.g. when no standard
+    /// stream is a terminal). See [`terminal_size`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use overture::prelude::*;
+    ///
+    /// let mut engine = OvertureRenderEngine::fit_terminal().unwrap();
+    /// let size = engine.size();
+    /// engine.load_renderable(primitives::shape::SoftBox::new(DiscreteCoord::ORIGIN, size - DiscreteCoord::new(1, 1)), None);
+    /// engine.render(size.y as u16).unwrap();
+    /// ```
+    #[cfg(unix)]
+    pub fn fit_terminal() -> io::Result<Self> {
+        let size = terminal_size()?;
+        Ok(Self::new(size.x, size.y))
+    }
+
+    /// Returns the size of the buffer as (columns, rows).
+    pub fn size(&self) -> DiscreteCoord {
+        DiscreteCoord::new(self.width, self.buffer.len() as u32)
+    }
+
+    /// Resizes the buffer to exactly `width` columns and `height` rows.
+    ///
+    /// Rows and columns are added or dropped at the bottom and right. Every object is then
+    /// placed again, so objects anchored with a [`RenderPlacementConfig`] such as `CenterStage`
+    /// follow the new size, and content that was clipped by the old size reappears when there is
+    /// room again. The next frame is fully repainted.
+    ///
+    /// Content below the new height is cut off. Like any content, it grows the buffer again
+    /// when it is next drawn, e.g. when its layer is shown or the object is replaced.
+    ///
+    /// # Example
+    /// ```
+    /// use overture::prelude::*;
+    ///
+    /// let mut engine = OvertureRenderEngine::new(10, 2);
+    /// engine.load_renderable(primitives::Text::new("truncated", DiscreteCoord::ORIGIN), None);
+    ///
+    /// engine.resize(5, 1);
+    /// assert_eq!(engine.to_plain_string(), "trunc");
+    /// engine.resize(12, 1);
+    /// assert_eq!(engine.to_plain_string(), "truncated   ");
+    /// ```
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.buffer.resize(height as usize, vec![]);
+        for line in self.buffer.iter_mut() {
+            line.resize(width as usize, RenderChar::BLANK_RENDER_CHAR);
         }
+        self.owners.resize(height as usize, vec![]);
+        for line in self.owners.iter_mut() {
+            line.resize(width as usize, None);
+        }
+        for layer in 0..self.layers.len() {
+            for index in 0..self.layers[layer].entries.len() {
+                let object = &self.layers[layer].entries[index];
+                self.layers[layer].entries[index].placed = self.place(&object.source, object.placement);
+            }
+        }
+        self.invalidate();
+        self.recompose();
+        self.buffer.truncate(height as usize);
+        self.owners.truncate(height as usize);
+    }
+
+    /// Resizes the buffer to the current terminal size if it changed (Unix only).
+    ///
+    /// Call this once per frame, or after a [`take_resize`](crate::ioopts::terminal::take_resize)
+    /// or an [`Event::Resize`](crate::ioopts::input::Event::Resize).
+    ///
+    /// # Returns
+    ///
+    /// `true` if the engine was resized and the application should re-layout.
+    #[cfg(unix)]
+    pub fn sync_terminal_size(&mut self) -> io::Result<bool> {
+        let size = terminal_size()?;
+        if size == self.size() {
+            return Ok(false);
+        }
+        self.resize(size.x, size.y);
+        Ok(true)
+    }
+
+    /// Replaces the engine's output sink.
+    ///
+    /// The next frame is fully repainted, since the new sink has not seen any previous output.
+    pub fn set_writer<W: Write + 'static>(&mut self, writer: W) {
+        self.sink = Box::new(writer);
+        self.invalidate();
     }
 
     // Sets a specific pixel in the terminal buffer.
@@ -122,6 +321,11 @@ impl OvertureRenderEngine {
     ///
     /// - The internal buffer grows **vertically** as needed (never shrinks).
     /// - Horizontal bounds are clamped to avoid index panics.
+    /// - The pixel is composited over the current cell according to its
+    ///   [`Transparency`](crate::interfaces::rendering::Transparency); opaque pixels replace it.
+    /// - The pixel is written to the buffer only, not to any [`Layer`], so it is lost the next
+    ///   time the layers are recomposed. It is not owned by any object, so
+    ///   [`hit_test`](OvertureRenderEngine::hit_test) returns `None` for the cell.
     ///
     /// # Example
     /// ```
@@ -131,39 +335,146 @@ impl OvertureRenderEngine {
     /// engine.set_pixel(10, 5, RenderChar::new_plain('X'));
     /// ```
     pub fn set_pixel(&mut self, x: u32, y: u32, ch: RenderChar) {
+        self.put(x, y, ch, None);
+    }
+
+    /// Composites `ch` over the cell at (`x`, `y`) on behalf of `owner`, growing the buffer as needed.
+    ///
+    /// Fully transparent characters leave the cell's owner untouched. Wide characters are kept
+    /// whole: writing one also writes its continuation into the next cell, and overwriting
+    /// either half of a wide character blanks the other half.
+    fn put(&mut self, x: u32, y: u32, ch: RenderChar, owner: Option<RenderId>) {
         let x = x as usize;
         let y = y as usize;
+        let width = self.width as usize;
+        self.grow_rows(y + 1);
+        if x >= width {
+            return;
+        }
 
-        while self.buffer.len() <= y {
-            self.buffer.push(vec![RenderChar::BLANK_RENDER_CHAR; self.width as usize]);
+        let old = self.buffer[y][x].clone();
+        let mut new = ch.over(&old);
+        if new.is_continuation() && (x == 0 || self.buffer[y][x - 1].width() != 2) {
+            // A continuation without its wide character
+            new.ch = RenderChar::BLANK_RENDER_CHAR.ch;
+        }
+        if new.width() == 2 && x + 1 >= width {
+            // Does not fit in the last column
+            new.ch = RenderChar::BLANK_RENDER_CHAR.ch;
         }
 
-        if x < self.width as usize {
-            self.buffer[y][x] = ch;
+        if old.is_continuation() && !new.is_continuation() && x > 0 {
+            Self::break_wide(&mut self.buffer[y][x - 1]);
         }
+        if new.width() == 2 {
+            if !self.buffer[y][x + 1].is_continuation() {
+                if self.buffer[y][x + 1].width() == 2 && x + 2 < width {
+                    Self::break_wide(&mut self.buffer[y][x + 2]);
+                }
+                self.buffer[y][x + 1] = RenderChar::continuation(new.style);
+                if ch.transparency != Transparency::Transparent {
+                    self.owners[y][x + 1] = owner;
+                }
+            }
+        } else if old.width() == 2 && x + 1 < width {
+            Self::break_wide(&mut self.buffer[y][x + 1]);
+        }
+
+        if ch.transparency != Transparency::Transparent {
+            self.owners[y][x] = owner;
+        }
+        self.buffer[y][x] = new;
+    }
+
+    /// Blanks `cell`, the other half of a wide character that was partially overwritten,
+    /// keeping its style.
+    fn break_wide(cell: &mut RenderChar) {
+        cell.ch = RenderChar::BLANK_RENDER_CHAR.ch;
+    }
+
+    /// Appends blank rows until the buffer has at least `rows` rows.
+    fn grow_rows(&mut self, rows: usize) {
+        while self.buffer.len() < rows {
+            self.buffer.push(vec![RenderChar::BLANK_RENDER_CHAR; self.width as usize]);
+        }
+        while self.owners.len() < rows {
+            self.owners.push(vec![None; self.width as usize]);
+        }
+    }
+
+    /// Returns the id of the object that drew the cell at `pos`, if any.
+    ///
+    /// Positions are buffer coordinates, the same as those of mouse events. Cells drawn with
+    /// [`set_pixel`](OvertureRenderEngine::set_pixel), blank cells and cells outside the buffer
+    /// have no owner.
+    ///
+    /// # Example
+    /// ```
+    /// use overture::prelude::*;
+    ///
+    /// let mut engine = OvertureRenderEngine::new(20, 3);
+    /// let ok = engine.load_renderable(primitives::Text::new("[ OK ]", DiscreteCoord::new(2, 1)), None);
+    /// let cancel = engine.load_renderable(primitives::Text::new("[ Cancel ]", DiscreteCoord::new(10, 1)), None);
+    ///
+    /// assert_eq!(engine.hit_test(DiscreteCoord::new(4, 1)), Some(ok));
+    /// assert_eq!(engine.hit_test(DiscreteCoord::new(12, 1)), Some(cancel));
+    /// assert_eq!(engine.hit_test(DiscreteCoord::new(0, 0)), None);
+    /// ```
+    pub fn hit_test(&self, pos: DiscreteCoord) -> Option<RenderId> {
+        self.owners.get(pos.y as usize)?.get(pos.x as usize).copied().flatten()
+    }
+
+    /// Returns the layer the object with the given id was loaded into, if it is still there.
+    pub fn layer_of(&self, id: RenderId) -> Option<&Layer> {
+        self.layers.iter().find(|l| l.contains(id))
     }
 
     /// Clears the terminal screen by issuing the appropriate ANSI sequence
-    /// and flushing `stdout`.
+    /// and flushing the output sink.
     ///
     /// Typically called at the start of each frame or before rendering a new scene.
+    /// Since the screen no longer shows the previous frame, the next call to
+    /// [`render`](OvertureRenderEngine::render) performs a full repaint.
     ///
     /// # Example
     /// ```
     /// use overture::prelude::*;
     /// 
     /// let mut engine = OvertureRenderEngine::new(80, 24);
-    /// engine.flush(); // Clears screen before a fresh render
+    /// engine.flush().unwrap(); // Clears screen before a fresh render
     /// ```
-    pub fn flush(&self) {
-        println!("{}", crate::ioopts::ansi::cursor::CLEAR_SCREEN);
-        std::io::stdout().flush().unwrap();
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.invalidate();
+        writeln!(self.sink, "{}", cursor::CLEAR_SCREEN)?;
+        self.sink.flush()
     }
 
-    /// Renders the current screen buffer to the terminal.
+    /// Discards the remembered front frame, forcing the next [`render`](OvertureRenderEngine::render)
+    /// to repaint every cell.
     ///
-    /// This method walks through each character in the internal buffer and prints them
-    /// row by row, applying ANSI styling sequences as needed.
+    /// Use this when the terminal contents may no longer match what the engine last drew,
+    /// e.g. after other output was written to the terminal or the window was resized.
+    ///
+    /// # Example
+    /// ```
+    /// use overture::prelude::*;
+    /// 
+    /// let mut engine = OvertureRenderEngine::new(80, 24);
+    /// engine.render(24).unwrap();
+    /// engine.invalidate();
+    /// engine.render(24).unwrap(); // Repaints the full frame
+    /// ```
+    pub fn invalidate(&mut self) {
+        self.front = None;
+    }
+
+    /// Renders the current screen buffer to the engine's output sink.
+    ///
+    /// On the first call (or after [`invalidate`](OvertureRenderEngine::invalidate) or
+    /// [`flush`](OvertureRenderEngine::flush)), the cursor is moved home and every row of the
+    /// buffer is printed. On subsequent calls, the buffer is compared against the previously
+    /// rendered frame and only the changed cells are printed, each run of changes being
+    /// preceded by an absolute cursor movement.
     ///
     /// # Parameters
     ///
@@ -172,66 +483,269 @@ impl OvertureRenderEngine {
     ///
     /// # Styling Behavior
     ///
-    /// Styled characters (`RenderStyle::Styled`) are recursively printed with nested styles applied.
-    /// After each character, ANSI reset (`\x1b[0m`) is emitted to avoid style leakage.
+    /// The engine tracks the style the terminal is currently in and, before each cell, emits only
+    /// the minimal SGR transition to that cell's [`RenderStyle`]. Runs of identically styled cells
+    /// therefore share a single escape sequence. The terminal is reset (`\x1b[0m`) only when that
+    /// is shorter than turning individual attributes off, and always at the end of the frame.
     ///
     /// # Performance Notes
     ///
-    /// This method performs a full buffer flush to stdout. For fine-grained updates,
-    /// a smarter diff-based renderer may be layered on top later.
+    /// The whole frame is assembled in memory and handed to the sink in a single write.
+    /// If the buffer dimensions changed since the last frame, a full repaint is performed instead
+    /// of a diff. After rendering, the cursor is left on the line right below the frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error raised by the sink (e.g. a closed pipe). The next frame is then
+    /// fully repainted, since the terminal state is unknown.
     ///
     /// # Example
     /// ```
     /// use overture::prelude::*;
     /// 
     /// let mut engine = OvertureRenderEngine::new(80, 24);
-    /// engine.render(24); // Renders a 24-line frame to terminal
+    /// engine.render(24).unwrap(); // Renders a 24-line frame to terminal
+    /// engine.set_pixel(0, 0, RenderChar::new_plain('X'));
+    /// engine.render(24).unwrap(); // Only redraws the cell at (0, 0)
     /// ```
-    pub fn render(&mut self, height: u16) {
-        // Pad buffer to required height
-        while self.buffer.len() < height as usize {
-            self.buffer.push(vec![RenderChar::BLANK_RENDER_CHAR; self.width as usize]);
+    pub fn render(&mut self, height: u16) -> io::Result<()> {
+        let frame = self.compose_frame(height);
+        let result = self.sink.write_all(frame.as_bytes()).and_then(|_| self.sink.flush());
+        if result.is_err() {
+            self.invalidate();
         }
+        result
+    }
 
-        for line in &self.buffer {
-            for ch in line {
-                match &ch.style {
-                    RenderStyle::Plain => print!("{}", ch.ch),
-                    RenderStyle::Styled(seq, boxed_style) => {
-                        print!("{}", seq.to_esc_code());
-                        match **boxed_style {
-                            RenderStyle::Plain => print!("{}", ch.ch),
-                            RenderStyle::Styled(_, _) => {
-                                let inner_ch = RenderChar {
-                                    ch: ch.ch,
-                                    style: *boxed_style.clone(),
-                                };
-                                match inner_ch.style {
-                                    RenderStyle::Nil => print!("{}", RenderChar::BLANK_RENDER_CHAR.ch),
-                                    RenderStyle::Plain => print!("{}", inner_ch.ch),
-                                    RenderStyle::Styled(_, _) => {
-                                        print!("{}", inner_ch.ch);
-                                    }
-                                }
-                            },
-                            RenderStyle::Nil => print!("{}", RenderChar::BLANK_RENDER_CHAR.ch)
+    /// Renders the current screen buffer into the given writer instead of the engine's sink.
+    ///
+    /// Behaves exactly like [`render`](OvertureRenderEngine::render), including diffing against
+    /// the previously rendered frame, whichever writer that frame went to.
+    ///
+    /// # Example
+    /// ```
+    /// use overture::prelude::*;
+    /// 
+    /// let mut engine = OvertureRenderEngine::new(4, 1);
+    /// engine.set_pixel(0, 0, RenderChar::new_plain('X'));
+    /// 
+    /// let mut out: Vec<u8> = Vec::new();
+    /// engine.render_to(&mut out, 1).unwrap();
+    /// assert!(String::from_utf8(out).unwrap().contains("X   "));
+    /// ```
+    pub fn render_to<W: Write + ?Sized>(&mut self, out: &mut W, height: u16) -> io::Result<()> {
+        let frame = self.compose_frame(height);
+        let result = out.write_all(frame.as_bytes()).and_then(|_| out.flush());
+        if result.is_err() {
+            self.invalidate();
+        }
+        result
+    }
+
+    /// Assembles the output for the next frame and records the buffer as the new front frame.
+    fn compose_frame(&mut self, height: u16) -> String {
+        // Pad buffer and owners to required height
+        self.grow_rows(height as usize);
+
+        let mut out = String::new();
+        // Style the terminal is in while writing; every frame starts and ends reset.
+        let mut active = RenderStyle::PLAIN;
+        match &self.front {
+            Some(front) if front.len() == self.buffer.len()
+                && front.iter().zip(&self.buffer).all(|(a, b)| a.len() == b.len()) => {
+                for (y, (line, prev)) in self.buffer.iter().zip(front).enumerate() {
+                    // Column the cursor sits at after the last printed cell of this row, if any
+                    let mut cursor_x: Option<usize> = None;
+                    for (x, (ch, old)) in line.iter().zip(prev).enumerate() {
+                        // Wide characters are reprinted when either of their halves changed
+                        let width = ch.width() as usize;
+                        let changed = ch != old || (width == 2 && line.get(x + 1) != prev.get(x + 1));
+                        if ch.is_continuation() || !changed {
+                            continue;
+                        }
+                        match cursor_x {
+                            Some(cx) if cx == x => {},
+                            Some(_) => out.push_str(&cursor::move_to_column(x + 1)),
+                            None => out.push_str(&cursor::move_to(y + 1, x + 1)),
+                        }
+                        Self::push_cell(&mut out, &mut active, ch);
+                        cursor_x = Some(x + width);
+                    }
+                }
+                out.push_str(&cursor::move_to(self.buffer.len() + 1, 1));
+            }
+            _ => {
+                out.push_str(&cursor::move_to(1, 1));
+                for (y, line) in self.buffer.iter().enumerate() {
+                    if y > 0 {
+                        // Avoid painting the background color into the next line on scroll
+                        if active.bg.is_some() {
+                            Self::push_transition(&mut out, &mut active, RenderStyle::PLAIN);
                         }
-                        print!("{}", crate::ioopts::ansi::styling::RESET);
+                        out.push('\n');
                     }
-                    RenderStyle::Nil => todo!(),
+                    for ch in line {
+                        Self::push_cell(&mut out, &mut active, ch);
+                    }
+                }
+                // Moving rather than breaking the line keeps a full-screen frame from scrolling
+                out.push_str(&cursor::move_to(self.buffer.len() + 1, 1));
+            }
+        }
+        Self::push_transition(&mut out, &mut active, RenderStyle::PLAIN);
+        self.front = Some(self.buffer.clone());
+        out
+    }
+
+    /// Returns the current buffer as a string, one line per row, with ANSI styling applied.
+    ///
+    /// Unlike [`render`](OvertureRenderEngine::render), no cursor movements are emitted and the
+    /// remembered front frame is left untouched, so this never affects the next diff.
+    ///
+    /// # Example
+    /// ```
+    /// use overture::prelude::*;
+    /// 
+    /// let mut engine = OvertureRenderEngine::new(3, 1);
+    /// engine.set_pixel(1, 0, RenderChar::new('X', style![ANSISequence::FgRed]));
+    /// assert_eq!(engine.to_ansi_string(), " \x1b[31mX\x1b[0m ");
+    /// ```
+    pub fn to_ansi_string(&self) -> String {
+        self.buffer
+            .iter()
+            .map(|line| {
+                let mut out = String::new();
+                let mut active = RenderStyle::PLAIN;
+                for ch in line {
+                    Self::push_cell(&mut out, &mut active, ch);
+                }
+                Self::push_transition(&mut out, &mut active, RenderStyle::PLAIN);
+                out
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Returns the current buffer as plain text, one line per row, with all styling stripped.
+    ///
+    /// Cells styled with `RenderStyle::NIL` are shown as blanks. Continuation cells are
+    /// skipped, so every line has the buffer's width in display columns rather than in `char`s.
+    ///
+    /// # Example
+    /// ```
+    /// use overture::prelude::*;
+    /// 
+    /// let mut engine = OvertureRenderEngine::new(3, 2);
+    /// engine.set_pixel(1, 0, RenderChar::new('X', style![ANSISequence::FgRed]));
+    /// assert_eq!(engine.to_plain_string(), " X \n   ");
+    /// ```
+    pub fn to_plain_string(&self) -> String {
+        self.buffer
+            .iter()
+            .map(|line| {
+                let mut out = String::new();
+                for ch in line {
+                    Self::push_plain_cell(&mut out, ch);
                 }
+                out
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Iterates over every cell of the buffer in row-major order, together with its position.
+    ///
+    /// Each item exposes the full [`RenderChar`], including its [`RenderStyle`].
+    ///
+    /// # Example
+    /// ```
+    /// use overture::prelude::*;
+    /// 
+    /// let mut engine = OvertureRenderEngine::new(3, 1);
+    /// engine.set_pixel(1, 0, RenderChar::new('X', style![ANSISequence::FgRed]));
+    /// 
+    /// let styled: Vec<_> = engine.cells().filter(|(_, ch)| !ch.style.is_plain()).collect();
+    /// assert_eq!(styled.len(), 1);
+    /// assert_eq!(styled[0].0, DiscreteCoord::new(1, 0));
+    /// assert_eq!(styled[0].1.ch, 'X');
+    /// ```
+    pub fn cells(&self) -> impl Iterator<Item = (DiscreteCoord, &RenderChar)> {
+        self.buffer.iter().enumerate().flat_map(|(y, line)| {
+            line.iter()
+                .enumerate()
+                .map(move |(x, ch)| (DiscreteCoord::new(x as u32, y as u32), ch))
+        })
+    }
+
+    /// Returns a human-readable dump of every non-blank cell, one per line.
+    ///
+    /// Each line has the form `(x, y) 'c' <style>`, where `<style>` is the `Debug` form
+    /// of the cell's [`RenderStyle`]. Blank, unstyled cells are omitted.
+    ///
+    /// # Example
+    /// ```
+    /// use overture::prelude::*;
+    /// 
+    /// let mut engine = OvertureRenderEngine::new(3, 1);
+    /// engine.set_pixel(1, 0, RenderChar::new('X', style![ANSISequence::FgRed]));
+    /// assert_eq!(engine.dump_cells(), "(1, 0) 'X' RenderStyle { fg: Red }");
+    /// ```
+    pub fn dump_cells(&self) -> String {
+        self.cells()
+            .filter(|(_, ch)| **ch != RenderChar::BLANK_RENDER_CHAR && !ch.is_continuation())
+            .map(|(pos, ch)| format!("({}, {}) {:?} {:?}", pos.x, pos.y, ch.ch, ch.style))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Appends a single cell to `out`, preceded by the minimal SGR transition from the
+    /// `active` terminal style to the cell's style.
+    ///
+    /// Continuation cells produce nothing, as the wide character before them covers both cells.
+    fn push_cell(out: &mut String, active: &mut RenderStyle, ch: &RenderChar) {
+        if ch.is_continuation() {
+            return;
+        }
+        if ch.style.is_nil() {
+            // Nil cells are blanked out rather than printed
+            Self::push_transition(out, active, RenderStyle::PLAIN);
+        } else {
+            Self::push_transition(out, active, ch.style);
+        }
+        Self::push_plain_cell(out, ch);
+    }
+
+    /// Appends the text of a single cell to `out`, taking up exactly [`RenderChar::width`] columns.
+    ///
+    /// Nil cells are blanked out, and zero-width characters are drawn over a blank.
+    pub(crate) fn push_plain_cell(out: &mut String, ch: &RenderChar) {
+        if ch.is_continuation() {
+            return;
+        }
+        if ch.style.is_nil() {
+            for _ in 0..ch.width() {
+                out.push(' ');
             }
-            println!();
+            return;
         }
-        std::io::stdout().flush().unwrap();
+        if ch.ch.width() == 0 {
+            out.push(' ');
+        }
+        ch.ch.push_to(out);
+    }
+
+    /// Appends the SGR sequence switching the terminal from `active` to `to`, and records `to` as active.
+    fn push_transition(out: &mut String, active: &mut RenderStyle, to: RenderStyle) {
+        out.push_str(&to.transition_from(active));
+        *active = to;
     }
 
 
-        /// Loads a `Renderable` object into the rendering engine with optional placement logic.
+    /// Loads a `Renderable` object into the rendering engine with optional placement logic.
     ///
-    /// This method normalizes and positions a `Renderable` object into the screen buffer
+    /// This method normalizes and positions a `Renderable` object into the [`BASE_LAYER`]
     /// based on the given placement strategy. It computes the object's bounding box,
-    /// aligns it as specified, and then delegates the final rendering to the object itself.
+    /// aligns it as specified, and draws the placed pixels into the screen buffer.
     ///
     /// # Parameters
     ///
@@ -241,10 +755,11 @@ impl OvertureRenderEngine {
     ///
     /// # Behavior
     ///
-    /// - The object's pixels are **normalized** to start from `(0, 0)` relative to its own bounds.
+    /// - The object's pixels are **normalized** to start from `(0, 0)` relative to its own bounds
+    ///   to compute its size.
     /// - The engine then determines a **target anchor point** in the screen buffer based on the
     ///   placement configuration.
-    /// - Finally, the object is rendered at that position using its `render_at` method.
+    /// - Finally, the object's pixels are offset by that anchor and stored in the layer.
     ///
     /// # Placement Options
     ///
@@ -261,21 +776,174 @@ impl OvertureRenderEngine {
     /// ```
     /// use overture::prelude::*;
     /// 
-    /// let box = primitive::shape::SoftBox::new(DiscreteCoord::ORIGIN, DiscreteCoord::new(10, 20));
+    /// let frame = primitives::shape::SoftBox::new(DiscreteCoord::ORIGIN, DiscreteCoord::new(10, 20));
     /// 
     /// let mut engine = OvertureRenderEngine::new(80, 24);
-    /// engine.load_renderable(box, Some(RenderPlacementConfig::CenterStage));
+    /// engine.load_renderable(frame, Some(RenderPlacementConfig::CenterStage));
     /// ```
     ///
+    /// # Returns
+    ///
+    /// The id of the loaded object, as reported by [`hit_test`](OvertureRenderEngine::hit_test)
+    /// for the cells it draws.
+    ///
     /// # Notes
     ///
-    /// - If the renderable has no pixels (`pixels().is_empty()`), this method is a no-op.
+    /// - A renderable with no pixels draws nothing, but is still retained and can be replaced later.
     /// - Out-of-bounds rendering is silently ignored at the pixel level (clipped).
     /// - `protected` pixels are preserved across prunes and reflows.
-    pub fn load_renderable<T: Renderable>(&mut self, obj: T, placement: Option<RenderPlacementConfig>) {
+    pub fn load_renderable<T: Renderable>(&mut self, obj: T, placement: Option<RenderPlacementConfig>) -> RenderId {
+        self.load_renderable_on(BASE_LAYER, obj, placement)
+    }
+
+    /// Loads a `Renderable` object into the named layer, creating the layer with a `z_index`
+    /// of 0 if it does not exist yet.
+    ///
+    /// Placement works exactly as in [`load_renderable`](OvertureRenderEngine::load_renderable).
+    /// If the layer is visible, the object is drawn immediately, below any visible layer stacked
+    /// above it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use overture::prelude::*;
+    /// 
+    /// let mut engine = OvertureRenderEngine::new(20, 5);
+    /// engine.add_layer("popup", 5);
+    /// engine.load_renderable_on("popup", primitives::Text::new("Saved!", DiscreteCoord::ORIGIN), Some(RenderPlacementConfig::CenterStage));
+    /// ```
+    pub fn load_renderable_on<T: Renderable>(&mut self, layer: &str, obj: T, placement: Option<RenderPlacementConfig>) -> RenderId {
+        let id = RenderId(self.next_id);
+        self.next_id += 1;
+
+        let source = obj.pixels();
+        let placed = self.place(&source, placement);
+
+        let index = match self.layer_index(layer) {
+            Some(index) => index,
+            None => {
+                self.add_layer(layer, 0);
+                self.layer_index(layer).unwrap()
+            }
+        };
+        let cells: HashSet<DiscreteCoord> = placed.iter().map(|p| p.position).collect();
+        let covered = self.layers[index + 1..]
+            .iter()
+            .any(|l| l.visible && !l.entries.is_empty());
+        let visible = self.layers[index].visible;
+
+        if visible && !covered {
+            let drawn = match &self.layers[index].mask {
+                Some(mask) => placed.clip(mask),
+                None => placed.clone(),
+            };
+            for pixel in drawn {
+                self.put(pixel.position.x, pixel.position.y, pixel.content, Some(id));
+            }
+        }
+        self.layers[index].entries.push(SceneObject { id, placement, visible: true, source, placed });
+        if visible && covered {
+            // Translucent pixels above must be blended over the new content, so redraw its cells
+            self.grow_rows(cells.iter().map(|p| p.y as usize + 1).max().unwrap_or(0));
+            self.refresh(&cells);
+        }
+        id
+    }
+
+    /// Returns the object with the given id, if it is still loaded.
+    pub fn object(&self, id: RenderId) -> Option<&SceneObject> {
+        self.layers.iter().flat_map(|l| l.entries.iter()).find(|o| o.id == id)
+    }
+
+    /// Replaces the object with the given id by `obj`, keeping its id, layer and placement.
+    ///
+    /// Only the cells covered by the old or the new object are redrawn.
+    /// Returns `false` if no such object exists.
+    ///
+    /// # Example
+    /// ```
+    /// use overture::prelude::*;
+    ///
+    /// let mut engine = OvertureRenderEngine::new(12, 1);
+    /// let status = engine.load_renderable(primitives::Text::new("Loading...", DiscreteCoord::ORIGIN), None);
+    /// engine.replace_object(status, primitives::Text::new("Done", DiscreteCoord::ORIGIN));
+    /// assert_eq!(engine.to_plain_string(), "Done        ");
+    /// ```
+    pub fn replace_object<T: Renderable>(&mut self, id: RenderId, obj: T) -> bool {
+        let source = obj.pixels();
+        self.update_object(id, |o| o.source = source)
+    }
+
+    /// Applies `style` to every pixel of the object with the given id, replacing their styles.
+    ///
+    /// Returns `false` if no such object exists.
+    ///
+    /// # Example
+    /// ```
+    /// use overture::prelude::*;
+    ///
+    /// let mut engine = OvertureRenderEngine::new(5, 1);
+    /// let label = engine.load_renderable(primitives::Text::new("alert", DiscreteCoord::ORIGIN), None);
+    /// engine.restyle_object(label, style![ANSISequence::FgRed]);
+    /// assert_eq!(engine.buffer[0][0].style, style![ANSISequence::FgRed]);
+    /// ```
+    pub fn restyle_object(&mut self, id: RenderId, style: RenderStyle) -> bool {
+        self.update_object(id, |o| {
+            for pixel in o.source.iter_mut() {
+                pixel.content.style = style;
+            }
+        })
+    }
+
+    /// Shows or hides the object with the given id without discarding it.
+    ///
+    /// Returns `false` if no such object exists.
+    pub fn set_object_visible(&mut self, id: RenderId, visible: bool) -> bool {
+        self.update_object(id, |o| o.visible = visible)
+    }
+
+    /// Removes the object with the given id from the scene, returning it if it existed.
+    pub fn remove_object(&mut self, id: RenderId) -> Option<SceneObject> {
+        let (layer, index) = self.object_index(id)?;
+        let object = self.layers[layer].entries.remove(index);
+        self.refresh(&object.placed.iter().map(|p| p.position).collect());
+        Some(object)
+    }
+
+    /// Applies `f` to the object with the given id, re-places it and redraws the affected cells.
+    fn update_object(&mut self, id: RenderId, f: impl FnOnce(&mut SceneObject)) -> bool {
+        let Some((layer, index)) = self.object_index(id) else {
+            return false;
+        };
+        let mut cells: HashSet<DiscreteCoord> = self.layers[layer].entries[index].placed
+            .iter()
+            .map(|p| p.position)
+            .collect();
+
+        f(&mut self.layers[layer].entries[index]);
+        let object = &self.layers[layer].entries[index];
+        let placed = self.place(&object.source, object.placement);
+        cells.extend(placed.iter().map(|p| p.position));
+        self.layers[layer].entries[index].placed = placed;
+
+        if self.layers[layer].visible {
+            self.grow_rows(cells.iter().map(|p| p.y as usize + 1).max().unwrap_or(0));
+        }
+        self.refresh(&cells);
+        true
+    }
+
+    fn object_index(&self, id: RenderId) -> Option<(usize, usize)> {
+        self.layers.iter().enumerate().find_map(|(layer, l)| {
+            l.entries.iter().position(|o| o.id == id).map(|index| (layer, index))
+        })
+    }
+
+    /// Computes the absolute pixels of `obj` once anchored according to `placement`.
+    fn place<T: Renderable + ?Sized>(&self, obj: &T, placement: Option<RenderPlacementConfig>) -> Vec<Pixel> {
         let pixels = obj.pixels();
         if pixels.is_empty() {
-            return;
+            return pixels;
         }
 
         let min_x = pixels.iter().map(|p| p.position.x).min().unwrap_or(0);
@@ -288,7 +956,7 @@ impl OvertureRenderEngine {
             ), p.protected))
             .collect();
 
-        let obj_width = normalized_pixels.iter().map(|p| p.position.x).max().unwrap_or(0) + 1;
+        let obj_width = normalized_pixels.iter().map(Pixel::right_edge).max().unwrap_or(0) + 1;
         let obj_height = normalized_pixels.iter().map(|p| p.position.y).max().unwrap_or(0) + 1;
 
         let available_width = self.width.saturating_sub(obj_width);
@@ -308,7 +976,194 @@ impl OvertureRenderEngine {
             RenderPlacementConfig::Offset(offset)  => (max(offset.x, 0) as u32, max(offset.y, 0) as u32),
         };
 
-        let pos: DiscreteCoord = DiscreteCoord::new(x, y);
-        obj.render_at(pos.x, pos.y, self);
+        pixels
+            .into_iter()
+            .map(|p| Pixel::new(p.content, DiscreteCoord::new(x + p.position.x, y + p.position.y), p.protected))
+            .collect()
+    }
+
+    /// Adds an empty layer with the given name and `z_index`.
+    ///
+    /// If a layer with that name already exists, it is kept as is and only moved to the new
+    /// `z_index`. Layers sharing a `z_index` are composited in creation order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use overture::prelude::*;
+    /// 
+    /// let mut engine = OvertureRenderEngine::new(80, 24);
+    /// engine.add_layer("background", -1);
+    /// engine.add_layer("hud", 10);
+    /// 
+    /// let names: Vec<&str> = engine.layers().map(|l| l.name.as_str()).collect();
+    /// assert_eq!(names, ["background", "base", "hud"]);
+    /// ```
+    pub fn add_layer(&mut self, name: &str, z_index: i32) {
+        if !self.set_layer_z_index(name, z_index) {
+            self.layers.push(Layer::new(name, z_index));
+            self.layers.sort_by_key(|l| l.z_index);
+            self.recompose();
+        }
     }
-}
\ No newline at end of file
+
+    /// Removes the named layer and its content, returning it if it existed.
+    ///
+    /// The [`BASE_LAYER`] may be removed too; it is recreated by the next
+    /// [`load_renderable`](OvertureRenderEngine::load_renderable).
+    pub fn remove_layer(&mut self, name: &str) -> Option<Layer> {
+        let index = self.layer_index(name)?;
+        let layer = self.layers.remove(index);
+        self.recompose();
+        Some(layer)
+    }
+
+    /// Returns the named layer, if any.
+    pub fn layer(&self, name: &str) -> Option<&Layer> {
+        self.layers.iter().find(|l| l.name == name)
+    }
+
+    /// Iterates over all layers bottom-up, in composition order.
+    pub fn layers(&self) -> impl Iterator<Item = &Layer> {
+        self.layers.iter()
+    }
+
+    /// Shows or hides the named layer without discarding its content.
+    ///
+    /// Returns `false` if no such layer exists.
+    pub fn set_layer_visible(&mut self, name: &str, visible: bool) -> bool {
+        match self.layer_index(name) {
+            Some(index) => {
+                if self.layers[index].visible != visible {
+                    self.layers[index].visible = visible;
+                    self.recompose();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the named layer to a new `z_index`, keeping its content.
+    ///
+    /// A moved layer is placed after any existing layers sharing the new `z_index`.
+    /// Returns `false` if no such layer exists.
+    pub fn set_layer_z_index(&mut self, name: &str, z_index: i32) -> bool {
+        match self.layer_index(name) {
+            Some(index) => {
+                if self.layers[index].z_index != z_index {
+                    let mut layer = self.layers.remove(index);
+                    layer.z_index = z_index;
+                    let at = self.layers.partition_point(|l| l.z_index <= z_index);
+                    self.layers.insert(at, layer);
+                    self.recompose();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Sets or removes the mask of the named layer. Only cells covered by the mask are drawn
+    /// from the layer; its content is kept as is.
+    ///
+    /// Returns `false` if no such layer exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use overture::prelude::*;
+    /// use overture::interfaces::layers::rect_mask;
+    /// use overture::engine::BASE_LAYER;
+    /// 
+    /// let mut engine = OvertureRenderEngine::new(8, 1);
+    /// engine.load_renderable(primitives::Text::new("abcdefgh", DiscreteCoord::ORIGIN), None);
+    /// engine.set_layer_mask(BASE_LAYER, Some(rect_mask(DiscreteCoord::new(2, 0), DiscreteCoord::new(4, 0))));
+    /// assert_eq!(engine.to_plain_string(), "  cde   ");
+    /// ```
+    pub fn set_layer_mask(&mut self, name: &str, mask: Option<Mask>) -> bool {
+        match self.layer_index(name) {
+            Some(index) => {
+                self.layers[index].mask = mask;
+                self.recompose();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes every object from every layer, keeping the layers themselves.
+    ///
+    /// Handy for apps rebuilding their whole scene on each redraw. Unchanged cells are still not
+    /// re-emitted by the next [`render`](OvertureRenderEngine::render).
+    pub fn clear_scene(&mut self) {
+        for layer in self.layers.iter_mut() {
+            layer.entries.clear();
+        }
+        self.recompose();
+    }
+
+    /// Discards the content of the named layer, keeping the layer itself.
+    ///
+    /// Returns `false` if no such layer exists.
+    pub fn clear_layer(&mut self, name: &str) -> bool {
+        match self.layer_index(name) {
+            Some(index) => {
+                self.layers[index].entries.clear();
+                self.recompose();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Recomposes only the rows containing the given cells from every visible layer, bottom-up,
+    /// growing the buffer as needed.
+    ///
+    /// Whole rows are recomposed so that wide characters straddling the edge of the changed
+    /// region are redrawn intact.
+    fn refresh(&mut self, cells: &HashSet<DiscreteCoord>) {
+        let rows: HashSet<usize> = cells.iter().map(|pos| pos.y as usize).collect();
+        for &y in &rows {
+            if let (Some(line), Some(owners)) = (self.buffer.get_mut(y), self.owners.get_mut(y)) {
+                line.fill(RenderChar::BLANK_RENDER_CHAR);
+                owners.fill(None);
+            }
+        }
+        for index in 0..self.layers.len() {
+            for (owner, pixel) in self.layers[index].owned_pixels() {
+                if rows.contains(&(pixel.position.y as usize)) {
+                    self.put(pixel.position.x, pixel.position.y, pixel.content, Some(owner));
+                }
+            }
+        }
+    }
+
+    /// Rebuilds the buffer from scratch by compositing every visible layer bottom-up.
+    ///
+    /// Called automatically whenever the layer stack changes. Anything written directly with
+    /// [`set_pixel`](OvertureRenderEngine::set_pixel) is discarded.
+    pub fn recompose(&mut self) {
+        for line in self.buffer.iter_mut() {
+            line.fill(RenderChar::BLANK_RENDER_CHAR);
+        }
+        for line in self.owners.iter_mut() {
+            line.fill(None);
+        }
+        for index in 0..self.layers.len() {
+            self.paint_layer(index);
+        }
+    }
+
+    /// Draws the content of the layer at `index` into the buffer, if it is visible,
+    /// clipped to the layer's mask, growing the buffer as needed.
+    fn paint_layer(&mut self, index: usize) {
+        for (owner, pixel) in self.layers[index].owned_pixels() {
+            self.put(pixel.position.x, pixel.position.y, pixel.content, Some(owner));
+        }
+    }
+
+    fn layer_index(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|l| l.name == name)
+    }
+}
@@ -0,0 +1,100 @@
+// Created by Sean L. on Jun. 25.
+// Last Updated by Sean L. on Jun. 25.
+//
+// overture.rs
+// src/error.rs
+//
+// Makabaka1880, 2025. All rights reserved.
+
+//! Errors returned by the fallible parts of Overture.
+//!
+//! Font lookup, font parsing and ASCII-art conversion report failures as an [`OvertureError`]
+//! naming the font or character involved. Terminal IO errors, e.g. from
+//! [`OvertureRenderEngine::render`](crate::engine::OvertureRenderEngine::render), convert into
+//! [`OvertureError::Io`], so that `?` works across both in functions returning
+//! [`OvertureResult`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use overture::prelude::*;
+//! use overture::{OvertureError, OvertureResult};
+//!
+//! fn banner(engine: &mut OvertureRenderEngine, font: &str) -> OvertureResult<()> {
+//!     let art = primitives::Text::new("Hi", DiscreteCoord::ORIGIN).try_ascii_art_by_name(font)?;
+//!     engine.load_renderable(art, None);
+//!     engine.render(8)?;
+//!     Ok(())
+//! }
+//!
+//! let mut engine = OvertureRenderEngine::new(20, 8);
+//! match banner(&mut engine, "no-such-font") {
+//!     Err(OvertureError::UnknownFont { name, .. }) => assert_eq!(name, "no-such-font"),
+//!     other => panic!("unexpected {:?}", other),
+//! }
+//! ```
+
+use std::fmt;
+use std::io;
+
+/// An error returned by Overture.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum OvertureError {
+    /// No font with this name is registered.
+    UnknownFont {
+        /// The name that was looked up.
+        name: String,
+        /// The names of the fonts that are available.
+        available: Vec<String>,
+    },
+    /// A font could not be parsed.
+    InvalidFont {
+        /// The name of the font.
+        name: String,
+        /// What is wrong with it.
+        reason: String,
+    },
+    /// A font has no glyph for a character of the text being converted.
+    UnsupportedCharacter {
+        /// The name of the font.
+        font: String,
+        /// The character without a glyph.
+        ch: char,
+    },
+    /// Reading a font file or writing to the terminal failed.
+    Io(io::Error),
+}
+
+/// A `Result` whose error is an [`OvertureError`].
+pub type OvertureResult<T> = Result<T, OvertureError>;
+
+impl fmt::Display for OvertureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OvertureError::UnknownFont { name, available } => {
+                write!(f, "unknown font {:?} (available: {})", name, available.join(", "))
+            }
+            OvertureError::InvalidFont { name, reason } => write!(f, "invalid font {:?}: {}", name, reason),
+            OvertureError::UnsupportedCharacter { font, ch } => {
+                write!(f, "font {:?} has no glyph for {:?} (U+{:04X})", font, ch, *ch as u32)
+            }
+            OvertureError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for OvertureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OvertureError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for OvertureError {
+    fn from(e: io::Error) -> Self {
+        OvertureError::Io(e)
+    }
+}
@@ -96,6 +96,13 @@ impl RenderableList {
     }
 }
 
+impl Default for RenderableList {
+    /// Creates an empty `RenderableList`, equivalent to [`RenderableList::new`].
+    fn default() -> Self {
+        RenderableList::new()
+    }
+}
+
 /// An iterator over references to the `Renderable` objects in a `RenderableList`.
 pub struct RenderableListIter<'a> {
     current: Option<&'a RenderableList>,
@@ -72,7 +72,6 @@ use std::cmp::{max};
 /// let c = a.add(b);
 /// assert_eq!(c, DiscreteCoord { x: 6, y: 8 });
 /// ```
-
 pub trait UnsignedR2DVector {
     /// Returns the horizontal component of the vector.
     fn x(&self) -> u32;
@@ -111,7 +110,7 @@ pub trait UnsignedR2DVector {
 /// let sum = a + b;
 /// assert_eq!(sum, DiscreteCoord::new(3, 4));
 /// ```
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct DiscreteCoord {
     /// The x-coordinate of the point.
     pub x: u32,
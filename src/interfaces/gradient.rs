@@ -0,0 +1,187 @@
+// Created by Sean L. on Jun. 25.
+// Last Updated by Sean L. on Jun. 25.
+//
+// overture.rs
+// src/interfaces/gradient.rs
+//
+// Makabaka1880, 2025. All rights reserved.
+
+//! Color gradients across renderables.
+//!
+//! A [`Gradient`] blends 24-bit colors across the bounding box of a group of pixels, setting
+//! their foreground ([`ANSISequence::FgRGB`]) or background ([`ANSISequence::BgRGB`]) color.
+//! Because it is mapped over the pixels' own bounding box, a gradient looks the same wherever
+//! the pixels are, whether it is applied before or after [`align`](crate::interfaces::rendering::Renderable::align)
+//! or [`translate`](crate::interfaces::rendering::Renderable::translate).
+//!
+//! Gradients are applied with [`Stylable::gradient`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use overture::prelude::*;
+//! use overture::interfaces::gradient::{Gradient, GradientDirection};
+//!
+//! let logo = primitives::Text::new("Overture", DiscreteCoord::ORIGIN)
+//!     .ascii_art_by_name("big")
+//!     .rasterize()
+//!     .prune()
+//!     .gradient(&Gradient::rainbow(GradientDirection::Diagonal));
+//! ```
+
+use crate::interfaces::{
+    geometry::DiscreteCoord,
+    pixels::Pixel,
+    styling::{RenderStyle, Stylable},
+};
+use crate::ioopts::ansi::ANSISequence;
+
+/// The axis along which a [`Gradient`] changes color.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GradientDirection {
+    /// From the left edge to the right edge. The default.
+    #[default]
+    Horizontal,
+    /// From the top edge to the bottom edge.
+    Vertical,
+    /// From the top-left corner to the bottom-right corner. Rows count twice as much as
+    /// columns, as terminal cells are about twice as tall as they are wide.
+    Diagonal,
+}
+
+/// The color of a pixel a [`Gradient`] sets.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColorTarget {
+    /// The foreground color. The default.
+    #[default]
+    Foreground,
+    /// The background color.
+    Background,
+}
+
+/// A blend of 24-bit colors across the bounding box of a group of pixels.
+///
+/// The colors are evenly spaced stops, blended linearly. Configured with `with_*` methods.
+///
+/// # Examples
+///
+/// ```rust
+/// use overture::interfaces::gradient::{ColorTarget, Gradient, GradientDirection};
+///
+/// let sunset = Gradient::new([(255, 94, 77), (255, 195, 113)], GradientDirection::Vertical)
+///     .with_target(ColorTarget::Background);
+/// assert_eq!(sunset.color_at(0.5), (255, 145, 95));
+///
+/// let stripes = Gradient::new([(0, 0, 0), (255, 255, 255)], GradientDirection::Horizontal).with_repeat(2);
+/// assert_eq!(stripes.color_at(0.25), (128, 128, 128));
+/// assert_eq!(stripes.color_at(0.5), (0, 0, 0));
+/// assert_eq!(stripes.color_at(1.0), (255, 255, 255));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Gradient {
+    /// The colors blended, from the start of the gradient to its end, as `(r, g, b)`.
+    pub stops: Vec<(u8, u8, u8)>,
+
+    /// The axis along which the color changes.
+    pub direction: GradientDirection,
+
+    /// Whether the foreground or background color is set.
+    pub target: ColorTarget,
+
+    /// How many times the stops are run through across the bounding box. At least 1.
+    pub repeat: u32,
+}
+
+impl Gradient {
+    /// Creates a foreground gradient running once through `stops`.
+    ///
+    /// # Parameters
+    ///
+    /// - `stops`: The colors to blend, as `(r, g, b)`. Without stops, the gradient leaves
+    ///   colors unchanged; with a single one, it applies that color everywhere.
+    /// - `direction`: The axis along which the color changes.
+    pub fn new<I: IntoIterator<Item = (u8, u8, u8)>>(stops: I, direction: GradientDirection) -> Self {
+        Gradient {
+            stops: stops.into_iter().collect(),
+            direction,
+            target: ColorTarget::default(),
+            repeat: 1,
+        }
+    }
+
+    /// Creates a foreground gradient running once around the color wheel, from red through
+    /// yellow, green, cyan, blue and magenta back to red.
+    ///
+    /// Use [`with_repeat`](Gradient::with_repeat) for several cycles.
+    pub fn rainbow(direction: GradientDirection) -> Self {
+        Gradient::new(
+            [(255, 0, 0), (255, 255, 0), (0, 255, 0), (0, 255, 255), (0, 0, 255), (255, 0, 255), (255, 0, 0)],
+            direction,
+        )
+    }
+
+    /// Returns this gradient setting the given [`ColorTarget`].
+    pub fn with_target(mut self, target: ColorTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Returns this gradient running `repeat` times through its stops. Clamped to at least 1.
+    pub fn with_repeat(mut self, repeat: u32) -> Self {
+        self.repeat = repeat.max(1);
+        self
+    }
+
+    /// Returns the color at `t`, from `0.0` at the start of the gradient to `1.0` at its end.
+    ///
+    /// Values outside of that range are clamped. A gradient without stops is black.
+    pub fn color_at(&self, t: f32) -> (u8, u8, u8) {
+        let Some(&last) = self.stops.last() else { return (0, 0, 0) };
+        let repeat = self.repeat.max(1) as f32;
+        let t = t.clamp(0.0, 1.0) * repeat;
+        // Every run but the last starts over at its end; the last ends on the final stop
+        let t = if t >= repeat { 1.0 } else { t.fract() };
+
+        let segments = (self.stops.len() - 1) as f32;
+        let position = t * segments;
+        let index = (position as usize).min(self.stops.len() - 1);
+        let Some(&next) = self.stops.get(index + 1) else { return last };
+        let from = self.stops[index];
+        let f = position - index as f32;
+        let blend = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * f).round() as u8;
+        (blend(from.0, next.0), blend(from.1, next.1), blend(from.2, next.2))
+    }
+
+    /// Returns `style` with its color set to the color at `t`, see [`color_at`](Gradient::color_at).
+    pub fn style_at(&self, style: RenderStyle, t: f32) -> RenderStyle {
+        if self.stops.is_empty() {
+            return style;
+        }
+        let (r, g, b) = self.color_at(t);
+        style.with(match self.target {
+            ColorTarget::Foreground => ANSISequence::FgRGB(r, g, b),
+            ColorTarget::Background => ANSISequence::BgRGB(r, g, b),
+        })
+    }
+
+    /// Colors `pixels` across their bounding box, keeping the rest of their style.
+    pub(crate) fn apply(&self, pixels: Vec<Pixel>) -> Vec<Pixel> {
+        let Some(first) = pixels.first().map(|pixel| pixel.position) else { return pixels };
+        let (min, max) = pixels.iter().fold((first, first), |(min, max), pixel| {
+            let p = pixel.position;
+            (DiscreteCoord::new(min.x.min(p.x), min.y.min(p.y)), DiscreteCoord::new(max.x.max(p.x), max.y.max(p.y)))
+        });
+        let (width, height) = ((max.x - min.x) as f32, (max.y - min.y) as f32);
+
+        pixels.style_with(|pixel| {
+            let (dx, dy) = ((pixel.position.x - min.x) as f32, (pixel.position.y - min.y) as f32);
+            let (distance, length) = match self.direction {
+                GradientDirection::Horizontal => (dx, width),
+                GradientDirection::Vertical => (dy, height),
+                GradientDirection::Diagonal => (dx + 2.0 * dy, width + 2.0 * height),
+            };
+            let t = if length > 0.0 { distance / length } else { 0.0 };
+            self.style_at(pixel.content.style, t)
+        })
+    }
+}
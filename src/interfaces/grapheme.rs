@@ -0,0 +1,192 @@
+// Created by Sean L. on Jun. 25.
+// Last Updated by Sean L. on Jun. 25.
+//
+// overture.rs
+// src/interfaces/grapheme.rs
+//
+// Makabaka1880, 2025. All rights reserved.
+
+//! Defines [`Grapheme`], the content of a single terminal cell.
+//!
+//! A grapheme is what a reader perceives as one character: a plain `char`, but also a letter
+//! with combining accents (`é` written as `e` + U+0301), a flag (two regional indicators) or an
+//! emoji ZWJ sequence (`👩‍💻`). Each of these is drawn into one cell, or two for wide ones.
+//!
+//! Single characters, which includes all of ASCII, are stored inline and are as cheap to
+//! copy and compare as a `char`. Longer clusters are stored as a shared string, so cloning
+//! a cell never allocates.
+//!
+//! Use [`graphemes`] to split text into cells the way [`Text`](crate::primitives::Text) does.
+
+use std::fmt;
+use std::sync::Arc;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// The content of a single terminal cell: one extended grapheme cluster.
+///
+/// Compares equal to a `char` or `&str` holding the same text.
+///
+/// # Examples
+///
+/// ```rust
+/// use overture::interfaces::grapheme::Grapheme;
+///
+/// let a = Grapheme::from('a');
+/// assert_eq!(a, 'a');
+/// assert_eq!(a.width(), 1);
+///
+/// let accented = Grapheme::from("e\u{301}\u{302}");    // Two combining accents
+/// assert_eq!(accented.width(), 1);
+/// assert_eq!(accented.to_string(), "e\u{301}\u{302}");
+///
+/// let flag = Grapheme::from("🇯🇵");
+/// assert_eq!(flag.width(), 2);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Grapheme(Repr);
+
+/// Storage of a [`Grapheme`]. A cluster always holds more than one `char`, so that each
+/// grapheme has exactly one representation.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Repr {
+    Char(char),
+    Cluster(Arc<str>),
+}
+
+impl Grapheme {
+    /// Creates a grapheme holding a single character.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::interfaces::grapheme::Grapheme;
+    ///
+    /// const X: Grapheme = Grapheme::from_char('x');
+    /// assert_eq!(X.as_char(), Some('x'));
+    /// ```
+    pub const fn from_char(ch: char) -> Self {
+        Grapheme(Repr::Char(ch))
+    }
+
+    /// Returns the character if this grapheme is a single `char`, or `None` for a cluster.
+    pub fn as_char(&self) -> Option<char> {
+        match self.0 {
+            Repr::Char(ch) => Some(ch),
+            Repr::Cluster(_) => None,
+        }
+    }
+
+    /// Returns the first character of the grapheme, i.e. its base character.
+    pub fn base(&self) -> char {
+        match &self.0 {
+            Repr::Char(ch) => *ch,
+            Repr::Cluster(text) => text.chars().next().unwrap_or(' '),
+        }
+    }
+
+    /// Returns the number of terminal columns the grapheme takes up: 0, 1 or 2.
+    ///
+    /// Zero-width graphemes (e.g. a combining mark with nothing to attach to) return 0.
+    /// Control characters, which have no defined width, count as one column.
+    pub fn width(&self) -> u32 {
+        match &self.0 {
+            Repr::Char(ch) => UnicodeWidthChar::width(*ch).unwrap_or(1) as u32,
+            Repr::Cluster(text) => (UnicodeWidthStr::width(&**text) as u32).min(2),
+        }
+    }
+
+    /// Appends the text of the grapheme to `out`.
+    pub fn push_to(&self, out: &mut String) {
+        match &self.0 {
+            Repr::Char(ch) => out.push(*ch),
+            Repr::Cluster(text) => out.push_str(text),
+        }
+    }
+}
+
+impl From<char> for Grapheme {
+    fn from(ch: char) -> Self {
+        Grapheme::from_char(ch)
+    }
+}
+
+impl From<&str> for Grapheme {
+    /// Creates a grapheme from a string holding a single grapheme cluster.
+    ///
+    /// The string is taken as it is, without being segmented. An empty string gives a blank.
+    fn from(text: &str) -> Self {
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (None, _) => Grapheme::from_char(' '),
+            (Some(ch), None) => Grapheme::from_char(ch),
+            _ => Grapheme(Repr::Cluster(Arc::from(text))),
+        }
+    }
+}
+
+impl From<String> for Grapheme {
+    fn from(text: String) -> Self {
+        Grapheme::from(text.as_str())
+    }
+}
+
+impl PartialEq<char> for Grapheme {
+    fn eq(&self, other: &char) -> bool {
+        self.0 == Repr::Char(*other)
+    }
+}
+
+impl PartialEq<&str> for Grapheme {
+    fn eq(&self, other: &&str) -> bool {
+        match &self.0 {
+            Repr::Char(ch) => {
+                let mut buf = [0; 4];
+                ch.encode_utf8(&mut buf) == *other
+            }
+            Repr::Cluster(text) => **text == **other,
+        }
+    }
+}
+
+impl fmt::Display for Grapheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Repr::Char(ch) => write!(f, "{}", ch),
+            Repr::Cluster(text) => f.write_str(text),
+        }
+    }
+}
+
+impl fmt::Debug for Grapheme {
+    /// Formats a single character like a `char` (`'a'`) and a cluster like a string (`"e\u{301}"`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Repr::Char(ch) => write!(f, "{:?}", ch),
+            Repr::Cluster(text) => write!(f, "{:?}", text),
+        }
+    }
+}
+
+/// Splits `text` into the graphemes it is drawn as, one per cell.
+///
+/// Pure ASCII text is split by byte, without running the full segmentation.
+///
+/// # Examples
+///
+/// ```rust
+/// use overture::interfaces::grapheme::graphemes;
+///
+/// let cells = graphemes("ne\u{301}e 👩‍💻");
+/// assert_eq!(cells.len(), 5);
+/// assert_eq!(cells[1], "e\u{301}");
+/// assert_eq!(cells[4], "👩‍💻");
+/// ```
+pub fn graphemes(text: &str) -> Vec<Grapheme> {
+    if text.is_ascii() {
+        text.bytes().map(|b| Grapheme::from_char(b as char)).collect()
+    } else {
+        text.graphemes(true).map(Grapheme::from).collect()
+    }
+}
@@ -6,31 +6,180 @@
 // 
 // Makabaka1880, 2025. All rights reserved.
 
-use crate::interfaces::rendering::Renderable;
+//! Layering and masking primitives for composition.
+//!
+//! A [`Layer`] is a named, z-ordered bucket of placed [`Pixel`]s. The render engine keeps a
+//! stack of layers and composites the visible ones bottom-up (lowest `z_index` first) into its
+//! buffer, so layers can be hidden, shown or reordered without reloading their content.
+//!
+//! A [`Mask`] is a set of cells content may be drawn through. Masks can be built from rectangles
+//! ([`rect_mask`]) or from the footprint of any renderable ([`Renderable::footprint`]), combined
+//! with the [`Maskable`] set operations, used to clip renderables ([`Renderable::clip`]) or
+//! attached to a layer.
+//!
+//! # Example
+//!
+//! ```rust
+//! use overture::prelude::*;
+//!
+//! let mut engine = OvertureRenderEngine::new(10, 1);
+//! engine.add_layer("overlay", 1);
+//! engine.load_renderable_on("overlay", primitives::Text::new("top", DiscreteCoord::ORIGIN), None);
+//! engine.load_renderable(primitives::Text::new("bottom", DiscreteCoord::ORIGIN), None);
+//! assert_eq!(engine.to_plain_string(), "toptom    ");
+//!
+//! engine.set_layer_visible("overlay", false);
+//! assert_eq!(engine.to_plain_string(), "bottom    ");
+//! ```
 
-use super::geometry::DiscreteCoord;
+use std::collections::HashSet;
+use crate::engine::RenderId;
+use crate::interfaces::{
+    geometry::{DiscreteCoord, RenderPlacementConfig},
+    pixels::Pixel,
+    rendering::Renderable,
+};
 
-#[derive(Clone)]
+/// A single cell of a [`Mask`].
+///
+/// A cell with `masked` set lets content through; cells that are unset or not listed at all
+/// block it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct MaskPixel {
+    /// Whether content at this position is let through.
     pub masked: bool,
+    /// The position of this cell.
     pub pos: DiscreteCoord
 }
 
 impl MaskPixel {
-    fn new(pos: DiscreteCoord) -> Self {
+    /// Creates a mask cell letting content through at `pos`.
+    pub fn new(pos: DiscreteCoord) -> Self {
         MaskPixel { masked: true, pos }
     }
 }
 
+/// A set of cells through which content is drawn. See [`Maskable`] for set operations.
 pub type Mask = Vec<MaskPixel>;
 
-trait Maskable {
+/// Builds a mask covering every cell of the rectangle spanned by two opposite corners (inclusive).
+///
+/// # Examples
+///
+/// ```rust
+/// use overture::interfaces::{geometry::DiscreteCoord, layers::{rect_mask, Maskable}};
+///
+/// let mask = rect_mask(DiscreteCoord::new(3, 2), DiscreteCoord::new(1, 1));
+/// assert_eq!(mask.len(), 6);
+/// assert!(mask.covers(DiscreteCoord::new(2, 2)));
+/// ```
+pub fn rect_mask(p1: DiscreteCoord, p2: DiscreteCoord) -> Mask {
+    let (min, max) = (
+        DiscreteCoord::new(p1.x.min(p2.x), p1.y.min(p2.y)),
+        DiscreteCoord::new(p1.x.max(p2.x), p1.y.max(p2.y)),
+    );
+    (min.y..=max.y)
+        .flat_map(|y| (min.x..=max.x).map(move |x| MaskPixel::new(DiscreteCoord::new(x, y))))
+        .collect()
+}
+
+/// Trait for anything describing a set of cells, with set operations producing new [`Mask`]s.
+///
+/// All operations return normalized masks: every cell listed once, `masked` set, in row-major order.
+///
+/// # Examples
+///
+/// ```rust
+/// use overture::interfaces::{geometry::DiscreteCoord, layers::{rect_mask, Maskable}};
+///
+/// let a = rect_mask(DiscreteCoord::new(0, 0), DiscreteCoord::new(3, 0));
+/// let b = rect_mask(DiscreteCoord::new(2, 0), DiscreteCoord::new(5, 0));
+///
+/// assert_eq!(a.union(&b).len(), 6);
+/// assert_eq!(a.intersection(&b).len(), 2);
+/// assert_eq!(a.difference(&b).len(), 2);
+/// ```
+pub trait Maskable {
+    /// Returns the raw mask cells.
     fn mask(&self) -> Vec<MaskPixel>;
+
+    /// Returns the set of positions letting content through.
+    fn covered(&self) -> HashSet<DiscreteCoord> {
+        self.mask().into_iter().filter(|m| m.masked).map(|m| m.pos).collect()
+    }
+
+    /// Returns `true` if content at `pos` is let through.
+    fn covers(&self, pos: DiscreteCoord) -> bool {
+        self.mask().iter().any(|m| m.masked && m.pos == pos)
+    }
+
+    /// Returns the cells covered by either mask.
+    fn union(&self, other: &dyn Maskable) -> Mask where Self: Sized {
+        let mut set = self.covered();
+        set.extend(other.covered());
+        from_set(set)
+    }
+
+    /// Returns the cells covered by both masks.
+    fn intersection(&self, other: &dyn Maskable) -> Mask where Self: Sized {
+        let other = other.covered();
+        from_set(self.covered().into_iter().filter(|p| other.contains(p)).collect())
+    }
+
+    /// Returns the cells covered by this mask but not by `other`.
+    fn difference(&self, other: &dyn Maskable) -> Mask where Self: Sized {
+        let other = other.covered();
+        from_set(self.covered().into_iter().filter(|p| !other.contains(p)).collect())
+    }
+
+    /// Returns the cells of the rectangle spanned by `p1` and `p2` not covered by this mask.
+    fn invert_within(&self, p1: DiscreteCoord, p2: DiscreteCoord) -> Mask where Self: Sized {
+        rect_mask(p1, p2).difference(self)
+    }
+
+    /// Returns the cells of this mask's own bounding box that it does not cover.
+    ///
+    /// Inverting the footprint of an outline, such as a [`SoftBox`](crate::primitives::shape::SoftBox),
+    /// yields its interior.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::prelude::*;
+    /// use overture::interfaces::layers::Maskable;
+    ///
+    /// let panel = primitives::shape::SoftBox::new(DiscreteCoord::ORIGIN, DiscreteCoord::new(4, 3));
+    /// let interior = panel.footprint().invert();
+    /// assert_eq!(interior.len(), 3 * 2);
+    /// assert!(!interior.covers(DiscreteCoord::ORIGIN));
+    /// ```
+    fn invert(&self) -> Mask where Self: Sized {
+        let covered = self.covered();
+        if covered.is_empty() {
+            return vec![];
+        }
+        let min = DiscreteCoord::new(
+            covered.iter().map(|p| p.x).min().unwrap_or(0),
+            covered.iter().map(|p| p.y).min().unwrap_or(0),
+        );
+        let max = DiscreteCoord::new(
+            covered.iter().map(|p| p.x).max().unwrap_or(0),
+            covered.iter().map(|p| p.y).max().unwrap_or(0),
+        );
+        self.invert_within(min, max)
+    }
+}
+
+/// Converts a set of positions into a normalized, row-major [`Mask`].
+fn from_set(set: HashSet<DiscreteCoord>) -> Mask {
+    let mut cells: Vec<DiscreteCoord> = set.into_iter().collect();
+    cells.sort_by_key(|p| (p.y, p.x));
+    cells.into_iter().map(MaskPixel::new).collect()
 }
 
 impl Maskable for MaskPixel {
     fn mask(&self) -> Vec<MaskPixel> {
-        vec![self.clone()]
+        vec![*self]
     }
 }
 
@@ -40,7 +189,122 @@ impl Maskable for Mask {
     }
 }
 
+/// An object retained by the render engine, identified by the [`RenderId`] issued when it was loaded.
+///
+/// The engine keeps the pixels the object produced together with its placement, so the
+/// object can be re-placed (e.g. after a resize), restyled, hidden or replaced later.
+#[derive(Clone, Debug)]
+pub struct SceneObject {
+    /// The handle of this object.
+    pub id: RenderId,
+    /// The placement the object was loaded with.
+    pub placement: Option<RenderPlacementConfig>,
+    /// Whether this object takes part in composition.
+    pub visible: bool,
+    /// The pixels as produced by the renderable, before placement.
+    pub(crate) source: Vec<Pixel>,
+    /// The pixels at their absolute position in the buffer.
+    pub(crate) placed: Vec<Pixel>,
+}
+
+impl SceneObject {
+    /// Returns the object's pixels at their absolute position in the buffer.
+    pub fn pixels(&self) -> &[Pixel] {
+        &self.placed
+    }
+}
+
+/// A named, z-ordered group of placed pixels, composited by the render engine.
+///
+/// Layers with a higher `z_index` are drawn on top of lower ones; layers sharing a
+/// `z_index` are drawn in creation order. Hidden layers keep their content but are
+/// skipped during composition.
+///
+/// Each loaded object is kept as a [`SceneObject`], together with the [`RenderId`] the engine
+/// issued for it, so that drawn cells can be traced back to their object.
+///
+/// Layers are usually managed through the engine (see
+/// [`OvertureRenderEngine::add_layer`](crate::engine::OvertureRenderEngine::add_layer)),
+/// which recomposes its buffer whenever a layer changes.
+#[derive(Clone, Debug)]
 pub struct Layer {
-    // content: dyn Renderable,
-    pub mask: Option<Mask>
+    /// The unique name identifying this layer within an engine.
+    pub name: String,
+    /// The stacking order of this layer. Higher values are drawn on top.
+    pub z_index: i32,
+    /// Whether this layer takes part in composition.
+    pub visible: bool,
+    /// An optional mask; if set, only the cells it covers are drawn from this layer.
+    pub mask: Option<Mask>,
+    /// The objects loaded into this layer, in load order.
+    pub(crate) entries: Vec<SceneObject>,
+}
+
+impl Layer {
+    /// Creates a new, empty and visible layer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::interfaces::layers::Layer;
+    ///
+    /// let layer = Layer::new("hud", 10);
+    /// assert!(layer.visible);
+    /// assert!(layer.content().is_empty());
+    /// ```
+    pub fn new<S: Into<String>>(name: S, z_index: i32) -> Self {
+        Layer {
+            name: name.into(),
+            z_index,
+            visible: true,
+            mask: None,
+            entries: vec![],
+        }
+    }
+
+    /// Returns the placed pixels of every object in this layer, in load order, including hidden ones.
+    pub fn content(&self) -> Vec<Pixel> {
+        self.entries.iter().flat_map(|o| o.placed.iter().cloned()).collect()
+    }
+
+    /// Returns the objects loaded into this layer, in load order.
+    pub fn objects(&self) -> &[SceneObject] {
+        &self.entries
+    }
+
+    /// Returns `true` if an object with the given id was loaded into this layer.
+    pub fn contains(&self, id: RenderId) -> bool {
+        self.entries.iter().any(|o| o.id == id)
+    }
+
+    /// Returns the drawn pixels of every visible object, clipped to the mask, tagged with their
+    /// owner. Empty if the layer is hidden.
+    pub(crate) fn owned_pixels(&self) -> Vec<(RenderId, Pixel)> {
+        if !self.visible {
+            return vec![];
+        }
+        self.entries
+            .iter()
+            .filter(|o| o.visible)
+            .flat_map(|o| {
+                let pixels = match &self.mask {
+                    Some(mask) => o.placed.clip(mask),
+                    None => o.placed.clone(),
+                };
+                pixels.into_iter().map(move |p| (o.id, p))
+            })
+            .collect()
+    }
+}
+
+impl Renderable for Layer {
+    /// Returns the layer's content clipped to its mask, or nothing if the layer is hidden.
+    fn pixels(&self) -> Vec<Pixel> {
+        self.owned_pixels().into_iter().map(|(_, p)| p).collect()
+    }
+
+    /// Returns the bounding box dimension of the layer's content.
+    fn dim(&self) -> DiscreteCoord {
+        self.content().dim()
+    }
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
 // Created by Sean L. on Jun. 23.
-// Last Updated by Sean L. on Jun. 24.
+// Last Updated by Sean L. on Jun. 25.
 // 
 // Overture.rs
 // src/interfaces/mod.rs
@@ -11,16 +11,20 @@
 //! It provides:
 //! - `geometry`: 2D coordinates and layout configurations.
 //! - `rendering`: Traits and logic for rendering units to terminal.
+//! - `grapheme`: The content of a single terminal cell.
 //! - `pixel`: Representation of a renderable terminal cell.
 //! - `styling`: Style application using ANSI sequences.
+//! - `gradient`: Color gradients across renderables.
 //! - `rasterization`: Trait impls that convert structures into pixels.
 //! - `containers`: Renderable containers without type erasure.
 //! - `layers`: Provides API for opacity and layering
 
 pub mod geometry;
 pub mod rendering;
+pub mod grapheme;
 pub mod pixels;
 pub mod rasterization;
 pub mod styling;
+pub mod gradient;
 pub mod containers;
 pub mod layers;
\ No newline at end of file
@@ -1,5 +1,5 @@
 // Created by Sean L. on Jun. 23.
-// Last Updated by Sean L. on Jun. 24.
+// Last Updated by Sean L. on Jun. 25.
 // 
 // overture.rs
 // src/interfaces/bitmap.rs
@@ -11,9 +11,7 @@
 //! A `Pixel` bundles a [`RenderChar`] (character + style) with its absolute position ([`DiscreteCoord`]), 
 //! serving as the fundamental unit of rendering in the engine.
 
-use std::os::unix::process;
-
-use crate::interfaces::{rendering::RenderChar, geometry::DiscreteCoord};
+use crate::interfaces::{rendering::RenderChar, grapheme::Grapheme, geometry::DiscreteCoord};
 
 /// Represents a single drawable unit on the terminal screen.
 /// 
@@ -43,19 +41,20 @@ impl Pixel {
     /// ```rust
     /// use overture::interfaces::{pixels::Pixel, rendering::RenderChar, styling::RenderStyle, geometry::DiscreteCoord};
     /// 
-    /// let pixel = Pixel::new(RenderChar::new('A', RenderStyle::Plain), DiscreteCoord::new(10, 5), false);
+    /// let pixel = Pixel::new(RenderChar::new('A', RenderStyle::PLAIN), DiscreteCoord::new(10, 5), false);
     /// ```
     pub fn new(content: RenderChar, position: DiscreteCoord, protected: bool) -> Self {
         Pixel { content, position, protected }
     }
 
-    /// Convenience constructor to create a `Pixel` from a plain character and position.
+    /// Convenience constructor to create a `Pixel` from a plain character (or grapheme cluster)
+    /// and position.
     ///
     /// The created `Pixel` will have the default plain style.
     ///
     /// # Arguments
     ///
-    /// - `content` - The plain character to display.
+    /// - `content` - The plain character or grapheme to display.
     /// - `position` - The position of the pixel on the terminal grid.
     /// - `protected` - The prune protection flag of this pixel.
     /// 
@@ -66,7 +65,23 @@ impl Pixel {
     /// 
     /// let pixel = Pixel::new_with_char('A', DiscreteCoord::new(10, 5), false);
     /// ```
-    pub fn new_with_char(content: char, position: DiscreteCoord, protected: bool) -> Self {
+    pub fn new_with_char<G: Into<Grapheme>>(content: G, position: DiscreteCoord, protected: bool) -> Self {
         Pixel::new(RenderChar::new_plain(content), position, protected)
     }
+
+    /// Returns the x coordinate of the rightmost cell this pixel covers.
+    ///
+    /// This is `position.x + 1` for a wide character, and `position.x` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::interfaces::{pixels::Pixel, geometry::DiscreteCoord};
+    ///
+    /// assert_eq!(Pixel::new_with_char('a', DiscreteCoord::new(3, 0), false).right_edge(), 3);
+    /// assert_eq!(Pixel::new_with_char('漢', DiscreteCoord::new(3, 0), false).right_edge(), 4);
+    /// ```
+    pub fn right_edge(&self) -> u32 {
+        self.position.x + self.content.width().max(1) - 1
+    }
 }
@@ -1,5 +1,5 @@
 // Created by Sean L. on Jun. 23.
-// Last Updated by Sean L. on Jun. 24.
+// Last Updated by Sean L. on Jun. 25.
 // 
 // overture.rs
 // src/interfaces/clusters.rs
@@ -35,7 +35,7 @@ impl Renderable for Vec<Pixel> {
         } else {
             let (max_x, max_y) = self.iter().fold((0, 0), |(mx, my), p| {
                 (
-                    mx.max(p.position.x),
+                    mx.max(p.right_edge()),
                     my.max(p.position.y),
                 )
             });
@@ -48,13 +48,12 @@ impl Stylable for Vec<Pixel> {
     fn style(&self, style_seq: RenderStyle) -> Self {
         self.iter()
             .map(|x| {
-                let styled_content = match &style_seq {
-                    RenderStyle::Styled(seq, other) => {
-                        RenderChar::new(x.content.ch, RenderStyle::Styled(seq.to_owned(), other.to_owned()))
-                    }
-                    _ => x.content.clone(),
+                let styled_content = if style_seq.is_plain() || style_seq.is_nil() {
+                    x.content.clone()
+                } else {
+                    RenderChar { style: style_seq, ..x.content.clone() }
                 };
-                
+
                 Pixel::new(styled_content, x.position, x.protected)
             })
             .collect()
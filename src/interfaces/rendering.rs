@@ -1,5 +1,5 @@
 // Created by Sean L. on Jun. 23.
-// Last Updated by Sean L. on Jun. 24.
+// Last Updated by Sean L. on Jun. 25.
 // 
 // overture.rs
 // src/interfaces/render.rs
@@ -22,20 +22,65 @@
 use crate::{
     engine::OvertureRenderEngine,
     interfaces::{
-        geometry::{DiscreteCoord, RenderPlacementConfig, Translation}, pixels::Pixel, styling::RenderStyle
+        geometry::{DiscreteCoord, RenderPlacementConfig, Translation},
+        layers::{rect_mask, Mask, MaskPixel, Maskable},
+        grapheme::{graphemes, Grapheme},
+        pixels::Pixel,
+        styling::RenderStyle
     }
 };
-use std::{cmp::max, io::StderrLock};
+use unicode_width::UnicodeWidthChar;
+
+/// How a [`RenderChar`] combines with the cell it is drawn over.
+///
+/// Transparency is resolved whenever a character is composited onto another one, either in the
+/// engine buffer (see [`OvertureRenderEngine::set_pixel`]) or when overlaying pixel sets
+/// (see [`Renderable::overlay`]). The result of compositing is always [`Transparency::Opaque`].
+///
+/// # Examples
+/// ```rust
+/// use overture::interfaces::rendering::{RenderChar, Transparency};
+/// use overture::ioopts::ansi::ANSISequence;
+/// use overture::style;
+///
+/// let below = RenderChar::new('a', style![ANSISequence::FgRed]);
+/// let tint = RenderChar::new(' ', style![ANSISequence::BgBlue]).with_transparency(Transparency::KeepChar);
+///
+/// let result = tint.over(&below);
+/// assert_eq!(result.ch, 'a');
+/// assert_eq!(result.style, style![ANSISequence::FgRed, ANSISequence::BgBlue]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Transparency {
+    /// Replaces both the character and the style below. The default.
+    #[default]
+    Opaque,
+    /// Keeps the character below and layers this style on top of the style below:
+    /// colors that are set replace the ones below, attributes are added.
+    KeepChar,
+    /// Keeps the style below and only replaces the character.
+    KeepStyle,
+    /// Leaves the cell below untouched.
+    Transparent,
+}
 
 /// A single character intended for rendering in the terminal, with associated style.
 ///
-/// This struct encapsulates a `char` and a `RenderStyle`, allowing styled visual output.
+/// This struct encapsulates a [`Grapheme`], a `RenderStyle` and a [`Transparency`] mode,
+/// allowing styled visual output. The grapheme is usually a single `char`, but can be a whole
+/// cluster such as a letter with combining accents, a flag or an emoji ZWJ sequence.
+///
+/// # Display Width
+///
+/// Most characters occupy one terminal cell. Wide characters (CJK ideographs, most emoji)
+/// occupy two: the character itself in the left cell, and a
+/// [continuation](RenderChar::continuation) in the right one, which is never printed.
 ///
 /// # Examples
 /// ```rust
 /// use overture::interfaces::{rendering::RenderChar, styling::RenderStyle};
 /// use overture::ioopts::ansi::ANSISequence;
-/// use overture::style;            // Imports the style linked list constructor macro for easier styling
+/// use overture::style;            // Imports the style constructor macro for easier styling
 /// 
 /// let styled = RenderChar::new('X', style![ANSISequence::Bold, ANSISequence::BgBlue]);
 /// let plain = RenderChar::new_plain('X');     // Renders a bold `X`on a blue background
@@ -43,9 +88,11 @@ use std::{cmp::max, io::StderrLock};
 #[derive(Clone, Debug)]
 pub struct RenderChar {
     /// The character to be printed.
-    pub ch: char,
+    pub ch: Grapheme,
     /// The style applied to this character (e.g. bold, color).
     pub style: RenderStyle,
+    /// How this character combines with what is below it.
+    pub transparency: Transparency,
 }
 
 
@@ -57,15 +104,16 @@ impl RenderChar {
     /// ```rust
     /// use overture::interfaces::{rendering::RenderChar, styling::RenderStyle};
     /// use overture::ioopts::ansi::ANSISequence;
-    /// use overture::style;            // Imports the style linked list constructor macro for easier styling
+    /// use overture::style;            // Imports the style constructor macro for easier styling
     /// 
     /// let char = RenderChar::new('a', style![ANSISequence::FgRed]);     // Renders a red `a`
+    /// let flag = RenderChar::new("🇫🇷", style![ANSISequence::Bold]);     // Renders a French flag
     /// ```
-    pub fn new(ch: char, style: RenderStyle) -> Self {
-        RenderChar { ch: ch, style: style }
+    pub fn new<G: Into<Grapheme>>(ch: G, style: RenderStyle) -> Self {
+        RenderChar { ch: ch.into(), style, transparency: Transparency::Opaque }
     }
 
-    /// Creates a `RenderChar` with no style (`RenderStyle::Plain`).
+    /// Creates a `RenderChar` with no style (`RenderStyle::PLAIN`).
     /// 
     /// # Examples
     /// 
@@ -74,21 +122,115 @@ impl RenderChar {
     /// 
     /// let char = RenderChar::new_plain('a');      // Renders an `a` with no style
     /// ```
-    pub fn new_plain(ch: char) -> Self {
-        RenderChar { ch: ch, style: RenderStyle::Plain }
+    pub fn new_plain<G: Into<Grapheme>>(ch: G) -> Self {
+        RenderChar::new(ch, RenderStyle::PLAIN)
+    }
+
+    /// Returns this character with the given [`Transparency`] mode.
+    pub fn with_transparency(mut self, transparency: Transparency) -> Self {
+        self.transparency = transparency;
+        self
+    }
+
+    /// Creates the continuation occupying the right cell of a wide character, with the given style.
+    pub fn continuation(style: RenderStyle) -> Self {
+        RenderChar::new(Self::CONTINUATION_CHAR, style)
+    }
+
+    /// Returns `true` if this is the right cell of a wide character.
+    pub fn is_continuation(&self) -> bool {
+        self.ch == Self::CONTINUATION_CHAR
+    }
+
+    /// Returns the number of terminal cells this character occupies.
+    ///
+    /// This is 2 for wide characters, 0 for a [continuation](RenderChar::continuation) and
+    /// 1 otherwise. Zero-width graphemes count as one cell, as the engine draws them over a blank.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::interfaces::rendering::RenderChar;
+    ///
+    /// assert_eq!(RenderChar::new_plain('a').width(), 1);
+    /// assert_eq!(RenderChar::new_plain('漢').width(), 2);
+    /// assert_eq!(RenderChar::new_plain("👍🏽").width(), 2);
+    /// ```
+    pub fn width(&self) -> u32 {
+        if self.is_continuation() {
+            0
+        } else {
+            self.ch.width().max(1)
+        }
+    }
+
+    /// Composites this character over `below`, resolving its [`Transparency`].
+    ///
+    /// The returned character is always opaque.
+    pub fn over(&self, below: &RenderChar) -> RenderChar {
+        let (source, style) = match self.transparency {
+            Transparency::Opaque => (self, self.style),
+            Transparency::KeepChar => (below, self.style.over(&below.style)),
+            Transparency::KeepStyle => (self, below.style),
+            Transparency::Transparent => (below, below.style),
+        };
+        RenderChar { ch: source.ch.clone(), style, transparency: Transparency::Opaque }
     }
 
     /// A constant blank character with no style. Often used as a default or placeholder.
     pub const BLANK_RENDER_CHAR: RenderChar = RenderChar {
-        ch: ' ',
-        style: RenderStyle::Plain,
+        ch: Grapheme::from_char(' '),
+        style: RenderStyle::PLAIN,
+        transparency: Transparency::Opaque,
     };
+
+    /// The character marking a [continuation](RenderChar::continuation) cell.
+    pub const CONTINUATION_CHAR: char = '\0';
 }
 
 impl PartialEq for RenderChar {
-    /// Compares two `RenderChar`s by both character and style.
+    /// Compares two `RenderChar`s by character, style and transparency.
     fn eq(&self, other: &Self) -> bool {
-        self.ch == other.ch && self.style == other.style
+        self.ch == other.ch && self.style == other.style && self.transparency == other.transparency
+    }
+}
+
+/// Returns the number of terminal columns `ch` takes up: 0 for combining marks and other
+/// zero-width characters, 2 for wide characters and 1 otherwise.
+///
+/// Control characters, which have no defined width, count as one column.
+///
+/// # Examples
+///
+/// ```rust
+/// use overture::interfaces::rendering::char_width;
+///
+/// assert_eq!(char_width('a'), 1);
+/// assert_eq!(char_width('界'), 2);
+/// assert_eq!(char_width('\u{301}'), 0);
+/// ```
+pub fn char_width(ch: char) -> u32 {
+    UnicodeWidthChar::width(ch).unwrap_or(1) as u32
+}
+
+/// Returns the number of terminal columns `text` takes up on a single line, i.e. the number of
+/// cells its [graphemes](crate::interfaces::grapheme::graphemes) are drawn into.
+///
+/// # Examples
+///
+/// ```rust
+/// use overture::interfaces::rendering::str_width;
+///
+/// assert_eq!(str_width("hello"), 5);
+/// assert_eq!(str_width("日本"), 4);
+/// assert_eq!(str_width("e\u{301}"), 1);
+/// assert_eq!(str_width("🇯🇵!"), 3);
+/// ```
+pub fn str_width(text: &str) -> u32 {
+    if text.is_ascii() {
+        text.len() as u32
+    } else {
+        graphemes(text).iter().map(|g| g.width().max(1)).sum()
     }
 }
 
@@ -106,6 +248,8 @@ pub trait Renderable {
     fn dim(&self) -> DiscreteCoord;
 
     /// Renders this object at a specific position (`x`, `y`) in the given engine.
+    ///
+    /// Pixels are composited over the buffer through [`OvertureRenderEngine::set_pixel`].
     fn render_at(&self, x: u32, y: u32, engine: &mut OvertureRenderEngine) {
         let pixels = self.pixels();
         if pixels.is_empty() { return; }
@@ -137,7 +281,7 @@ pub trait Renderable {
 
         let min_x = pixels.iter().map(|p| p.position.x).min().unwrap_or(0);
         let min_y = pixels.iter().map(|p| p.position.y).min().unwrap_or(0);
-        let max_x = pixels.iter().map(|p| p.position.x).max().unwrap_or(0);
+        let max_x = pixels.iter().map(Pixel::right_edge).max().unwrap_or(0);
         let max_y = pixels.iter().map(|p| p.position.y).max().unwrap_or(0);
 
         let obj_width = max_x - min_x + 1;
@@ -190,13 +334,86 @@ pub trait Renderable {
     /// especially in generated or dynamically translated content.
     ///
     /// To prevent important blanks from being pruned, use [`protect`](#method.protect) or [`set_protect`](trait.Renderable.html#method.set_protect) beforehand.
+    ///
+    /// Fully [`Transparent`](Transparency::Transparent) pixels are always removed, while
+    /// [`KeepChar`](Transparency::KeepChar) pixels are always kept, since their character is
+    /// irrelevant.
     fn prune(&self) -> Vec<Pixel> {
         self.rasterize()
             .into_iter()
-            .filter(|pixel| (pixel.content.ch != RenderChar::BLANK_RENDER_CHAR.ch) || pixel.protected  )
+            .filter(|pixel| match pixel.content.transparency {
+                Transparency::Transparent => false,
+                Transparency::KeepChar => true,
+                _ => (pixel.content.ch != RenderChar::BLANK_RENDER_CHAR.ch) || pixel.protected,
+            })
             .collect()
     }
 
+    /// Returns a copy of the renderable's pixels with the given [`Transparency`] mode.
+    ///
+    /// # Use Case
+    ///
+    /// Tinting the background of a region without erasing the text below:
+    ///
+    /// ```rust
+    /// use overture::prelude::*;
+    /// use overture::interfaces::rendering::Transparency;
+    ///
+    /// let mut engine = OvertureRenderEngine::new(5, 1);
+    /// engine.load_renderable(primitives::Text::new("hello", DiscreteCoord::ORIGIN), None);
+    /// engine.load_renderable(
+    ///     primitives::shape::Fill::new(DiscreteCoord::new(1, 0), DiscreteCoord::new(3, 0))
+    ///         .with_transparency(Transparency::KeepChar)
+    ///         .style(style![ANSISequence::BgBlue]),
+    ///     None,
+    /// );
+    /// assert_eq!(engine.to_plain_string(), "hello");
+    /// assert_eq!(engine.buffer[0][2].style, style![ANSISequence::BgBlue]);
+    /// ```
+    fn with_transparency(&self, transparency: Transparency) -> Vec<Pixel> {
+        self.pixels()
+            .into_iter()
+            .map(|p| Pixel::new(p.content.with_transparency(transparency), p.position, p.protected))
+            .collect()
+    }
+
+    /// Composites the pixels of `top` over the pixels of this renderable.
+    ///
+    /// Where both have a pixel at the same position, the two are merged according to the top
+    /// pixel's [`Transparency`] (see [`RenderChar::over`]). Other pixels are kept as they are.
+    /// The result is in row-major order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::prelude::*;
+    /// use overture::interfaces::rendering::Transparency;
+    ///
+    /// let text = primitives::Text::new("abc", DiscreteCoord::ORIGIN);
+    /// let patch = primitives::Text::new("X", DiscreteCoord::new(1, 0))
+    ///     .with_transparency(Transparency::KeepStyle);
+    ///
+    /// let merged = text.overlay(&patch);
+    /// let chars: String = merged.iter().map(|p| p.content.ch.to_string()).collect();
+    /// assert_eq!(chars, "aXc");
+    /// ```
+    fn overlay(&self, top: &dyn Renderable) -> Vec<Pixel> {
+        let mut cells: std::collections::BTreeMap<(u32, u32), Pixel> = std::collections::BTreeMap::new();
+        for pixel in self.pixels().into_iter().chain(top.pixels()) {
+            let key = (pixel.position.y, pixel.position.x);
+            let merged = match cells.get(&key) {
+                Some(below) => Pixel::new(
+                    pixel.content.over(&below.content),
+                    pixel.position,
+                    pixel.protected || below.protected,
+                ),
+                None => pixel,
+            };
+            cells.insert(key, merged);
+        }
+        cells.into_values().collect()
+    }
+
     /// Returns a copy of the renderable's pixels, with all pixels marked as protected.
     ///
     /// This ensures that these pixels will **not** be removed by [`prune`](#method.prune), even if their character
@@ -230,6 +447,64 @@ pub trait Renderable {
         .collect()
     }
 
+    /// Returns a [`Mask`] covering every cell this renderable draws to.
+    ///
+    /// Combine it with the [`Maskable`] operations, e.g. [`invert`](Maskable::invert) to get the
+    /// interior of an outline.
+    fn footprint(&self) -> Mask {
+        self.pixels().iter().map(|p| MaskPixel::new(p.position)).collect()
+    }
+
+    /// Returns only the pixels of this renderable that fall inside the given mask.
+    ///
+    /// Wide characters are never cut in half: one whose other cell falls outside the mask
+    /// becomes a blank of the same style.
+    ///
+    /// # Use Case
+    ///
+    /// Keeping content inside a panel, e.g. clipping a long label to the interior of a `SoftBox`:
+    ///
+    /// ```rust
+    /// use overture::prelude::*;
+    /// use overture::interfaces::layers::Maskable;
+    ///
+    /// let panel = primitives::shape::SoftBox::new(DiscreteCoord::ORIGIN, DiscreteCoord::new(5, 2));
+    /// let label = primitives::Text::new("much too long", DiscreteCoord::new(1, 1))
+    ///     .clip(&panel.footprint().invert());
+    /// assert_eq!(label.len(), 4);
+    ///
+    /// // `字` would stick out of the mask into column 3
+    /// let cjk = primitives::Text::new("漢字", DiscreteCoord::ORIGIN)
+    ///     .clip_to_rect(DiscreteCoord::ORIGIN, DiscreteCoord::new(2, 0));
+    /// assert_eq!(cjk.len(), 3);
+    /// assert_eq!(cjk[2].content.ch, " ");
+    /// ```
+    fn clip(&self, mask: &dyn Maskable) -> Vec<Pixel> {
+        let covered = mask.covered();
+        self.pixels()
+            .into_iter()
+            .filter(|p| covered.contains(&p.position))
+            .map(|mut p| {
+                let DiscreteCoord { x, y } = p.position;
+                let whole = match p.content.width() {
+                    2 => covered.contains(&DiscreteCoord::new(x + 1, y)),
+                    0 => x > 0 && covered.contains(&DiscreteCoord::new(x - 1, y)),
+                    _ => true,
+                };
+                if !whole {
+                    p.content.ch = RenderChar::BLANK_RENDER_CHAR.ch;
+                }
+                p
+            })
+            .collect()
+    }
+
+    /// Returns only the pixels of this renderable inside the rectangle spanned by two opposite
+    /// corners (inclusive).
+    fn clip_to_rect(&self, p1: DiscreteCoord, p2: DiscreteCoord) -> Vec<Pixel> {
+        self.clip(&rect_mask(p1, p2))
+    }
+
 
 }
 
@@ -1,5 +1,5 @@
 // Created by Sean L. on Jun. 23.
-// Last Updated by Sean L. on Jun. 24.
+// Last Updated by Sean L. on Jun. 25.
 // 
 // overture.rs
 // src/interfaces/styling.rs
@@ -8,50 +8,502 @@
 
 //! Terminal styling system for [`Renderable`] objects in Overture.
 //!
-//! This module defines the [`RenderStyle`] enum for applying ANSI-based styling
+//! This module defines the [`RenderStyle`] struct for applying ANSI-based styling
 //! to characters rendered in the terminal, along with the [`Stylable`] trait
 //! to generically enable styling on any renderable element.
 //!
-//! `RenderStyle` supports recursive composition of ANSI sequences to model
-//! chained styling effects (e.g., bold + color).
+//! `RenderStyle` is a flat, `Copy` description of a cell's appearance: a foreground
+//! [`Color`], a background [`Color`], an optional underline [`Color`] and a set of
+//! [`Attributes`]. It is built by folding [`ANSISequence`]s into it, later sequences
+//! overriding earlier conflicting ones (e.g. two foreground colors).
+//!
+//! Besides applying one style to all of its pixels, a [`Stylable`] can be styled pixel by
+//! pixel with [`Stylable::style_with`], or colored with a [`Gradient`].
 
+use std::fmt;
+use std::ops::{BitOr, BitOrAssign};
 use crate::{
-    interfaces::{pixels::Pixel, rendering::Renderable}, ioopts::ansi::ANSISequence
+    interfaces::{gradient::Gradient, pixels::Pixel, rendering::Renderable}, ioopts::ansi::ANSISequence
 };
 
+/// A terminal color, either from the 16-color ANSI palette or a 24-bit RGB value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    /// A 24-bit color with red, green and blue channels.
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Returns the palette index (`0..16`) of this color, or `None` for [`Color::Rgb`].
+    pub fn palette_index(&self) -> Option<u8> {
+        Some(match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+            Color::BrightBlack => 8,
+            Color::BrightRed => 9,
+            Color::BrightGreen => 10,
+            Color::BrightYellow => 11,
+            Color::BrightBlue => 12,
+            Color::BrightMagenta => 13,
+            Color::BrightCyan => 14,
+            Color::BrightWhite => 15,
+            Color::Rgb(..) => return None,
+        })
+    }
+
+    /// SGR parameters selecting this color as foreground (`base` = 30) or background (`base` = 40).
+    fn sgr_params(&self, base: u8) -> String {
+        match (self, self.palette_index()) {
+            (Color::Rgb(r, g, b), _) => format!("{};2;{};{};{}", base + 8, r, g, b),
+            (_, Some(i)) if i < 8 => (base + i).to_string(),
+            (_, Some(i)) => (base + 60 + i - 8).to_string(),
+            (_, None) => unreachable!(),
+        }
+    }
+
+    /// SGR parameters selecting this color as underline color.
+    fn underline_sgr_params(&self) -> String {
+        match (self, self.palette_index()) {
+            (Color::Rgb(r, g, b), _) => format!("58;2;{};{};{}", r, g, b),
+            (_, Some(i)) => format!("58;5;{}", i),
+            (_, None) => unreachable!(),
+        }
+    }
+}
 
-/// Recursive enumeration for defining styling rules of a [`Pixel`] in a terminal UI.
+/// A compact bitset of SGR text attributes (bold, italic, ...).
 ///
-/// `RenderStyle` enables layering of ANSI sequences to create compound styles,
-/// such as bold + underline + color. It is implemented as a recursive enum for
-/// composability, mimicking a styling stack.
+/// # Example
+/// ```rust
+/// use overture::interfaces::styling::Attributes;
+///
+/// let attrs = Attributes::BOLD | Attributes::ITALIC;
+/// assert!(attrs.contains(Attributes::BOLD));
+/// assert!(!attrs.contains(Attributes::UNDERLINE));
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Attributes(u16);
+
+impl Attributes {
+    /// No attributes set.
+    pub const NONE: Attributes = Attributes(0);
+    /// Bold text style.
+    pub const BOLD: Attributes = Attributes(1 << 0);
+    /// Dim text style.
+    pub const DIM: Attributes = Attributes(1 << 1);
+    /// Italic text style.
+    pub const ITALIC: Attributes = Attributes(1 << 2);
+    /// Underlined text style.
+    pub const UNDERLINE: Attributes = Attributes(1 << 3);
+    /// Blinking text style.
+    pub const BLINK: Attributes = Attributes(1 << 4);
+    /// Inverted foreground and background colors.
+    pub const INVERT: Attributes = Attributes(1 << 5);
+    /// Hidden text (invisible).
+    pub const HIDDEN: Attributes = Attributes(1 << 6);
+    /// Strikethrough text style.
+    pub const STRIKETHROUGH: Attributes = Attributes(1 << 7);
+
+    /// Every attribute paired with its SGR parameter, its "off" SGR parameter and name, in SGR order.
+    ///
+    /// Note that bold and dim share the same "off" parameter (22).
+    const TABLE: [(Attributes, u8, u8, &'static str); 8] = [
+        (Attributes::BOLD, 1, 22, "BOLD"),
+        (Attributes::DIM, 2, 22, "DIM"),
+        (Attributes::ITALIC, 3, 23, "ITALIC"),
+        (Attributes::UNDERLINE, 4, 24, "UNDERLINE"),
+        (Attributes::BLINK, 5, 25, "BLINK"),
+        (Attributes::INVERT, 7, 27, "INVERT"),
+        (Attributes::HIDDEN, 8, 28, "HIDDEN"),
+        (Attributes::STRIKETHROUGH, 9, 29, "STRIKETHROUGH"),
+    ];
+
+    /// Returns `true` if every attribute in `other` is also set in `self`.
+    pub fn contains(&self, other: Attributes) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if no attribute is set.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Sets every attribute in `other`.
+    pub fn insert(&mut self, other: Attributes) {
+        self.0 |= other.0;
+    }
+
+    /// Clears every attribute in `other`.
+    pub fn remove(&mut self, other: Attributes) {
+        self.0 &= !other.0;
+    }
+
+    /// Iterates over the SGR parameters of the set attributes.
+    pub(crate) fn sgr_params(&self) -> impl Iterator<Item = u8> + '_ {
+        Self::TABLE.iter().filter(|(a, _, _, _)| self.contains(*a)).map(|(_, p, _, _)| *p)
+    }
+
+    /// Returns the SGR parameters turning `from` into `self`, without resetting the terminal.
+    fn transition_params(&self, from: Attributes) -> Vec<u8> {
+        let mut params = vec![];
+        let removed = Attributes(from.0 & !self.0);
+        let mut added = Attributes(self.0 & !from.0);
+
+        // 22 turns off both bold and dim, so whichever of the two should stay is re-enabled.
+        let intensity = Attributes::BOLD | Attributes::DIM;
+        if removed.0 & intensity.0 != 0 {
+            params.push(22);
+            added.insert(Attributes(self.0 & intensity.0));
+        }
+        for (a, _, off, _) in Self::TABLE.iter() {
+            if removed.contains(*a) && !intensity.contains(*a) {
+                params.push(*off);
+            }
+        }
+        params.extend(added.sgr_params());
+        params
+    }
+}
+
+impl BitOr for Attributes {
+    type Output = Attributes;
+
+    /// Returns the union of two attribute sets.
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Attributes(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Attributes {
+    /// Adds the attributes of `rhs` to `self` in-place.
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl fmt::Debug for Attributes {
+    /// Formats the set as `BOLD | ITALIC`, or `NONE` if empty.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "NONE");
+        }
+        let names: Vec<&str> = Self::TABLE
+            .iter()
+            .filter(|(a, _, _, _)| self.contains(*a))
+            .map(|(_, _, _, n)| *n)
+            .collect();
+        write!(f, "{}", names.join(" | "))
+    }
+}
+
+/// Flat description of the styling of a [`Pixel`] in a terminal UI.
+///
+/// A `RenderStyle` holds an optional foreground, background and underline color together
+/// with a set of [`Attributes`]. Unset colors fall back to the terminal defaults.
+/// Styles are usually built with the [`style!`](crate::style) macro, which folds
+/// [`ANSISequence`]s in order so that later sequences override earlier conflicting ones.
+///
+/// # Example
+/// ```rust
+/// use overture::ioopts::ansi::ANSISequence;
+/// use overture::interfaces::styling::{Attributes, Color, RenderStyle};
+/// use overture::style;
+///
+/// let style = style![ANSISequence::Bold, ANSISequence::FgRed, ANSISequence::FgBlue];
+/// assert_eq!(style.fg, Some(Color::Blue));
+/// assert!(style.attrs.contains(Attributes::BOLD));
+/// assert_eq!(style, RenderStyle::PLAIN.with(ANSISequence::Bold).with(ANSISequence::FgBlue));
+/// ```
 ///
 /// See also [`Stylable`] for applying styles to renderable items.
-#[derive(Clone, Debug, PartialEq)]
-pub enum RenderStyle {
-    /// Indicates an invisible style. Useful for explicitly blanking out characters.
-    Nil,
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RenderStyle {
+    /// The foreground color, or `None` for the terminal default.
+    pub fg: Option<Color>,
+    /// The background color, or `None` for the terminal default.
+    pub bg: Option<Color>,
+    /// The underline color, or `None` to underline in the foreground color.
+    pub underline_color: Option<Color>,
+    /// The text attributes (bold, italic, ...).
+    pub attrs: Attributes,
+    /// Marks an invisible style. Useful for explicitly blanking out characters.
+    nil: bool,
+}
 
+impl RenderStyle {
     /// The default terminal style, with no modifications applied.
-    Plain,
+    pub const PLAIN: RenderStyle = RenderStyle {
+        fg: None,
+        bg: None,
+        underline_color: None,
+        attrs: Attributes::NONE,
+        nil: false,
+    };
+
+    /// An invisible style. Useful for explicitly blanking out characters.
+    pub const NIL: RenderStyle = RenderStyle { nil: true, ..RenderStyle::PLAIN };
+
+    /// Returns `true` if this is the invisible [`RenderStyle::NIL`] style.
+    pub fn is_nil(&self) -> bool {
+        self.nil
+    }
+
+    /// Returns `true` if this style applies no modification to the terminal defaults.
+    pub fn is_plain(&self) -> bool {
+        *self == RenderStyle::PLAIN
+    }
+
+    /// Layers this style over `below`: colors set here replace the ones below, unset colors
+    /// show through, and attributes are combined. A nil style is fully see-through.
+    ///
+    /// # Example
+    /// ```rust
+    /// use overture::ioopts::ansi::ANSISequence;
+    /// use overture::style;
+    ///
+    /// let below = style![ANSISequence::FgRed, ANSISequence::Bold];
+    /// let tint = style![ANSISequence::BgBlue];
+    /// assert_eq!(tint.over(&below), style![ANSISequence::FgRed, ANSISequence::Bold, ANSISequence::BgBlue]);
+    /// ```
+    pub fn over(&self, below: &RenderStyle) -> RenderStyle {
+        if self.nil {
+            return *below;
+        }
+        RenderStyle {
+            fg: self.fg.or(below.fg),
+            bg: self.bg.or(below.bg),
+            underline_color: self.underline_color.or(below.underline_color),
+            attrs: self.attrs | below.attrs,
+            nil: false,
+        }
+    }
 
-    /// A styled terminal sequence applied atop another [`RenderStyle`] layer.
+    /// Returns this style with a single [`ANSISequence`] applied on top.
     ///
-    /// This variant allows recursive composition of ANSI styles by combining an [`ANSISequence`]
-    /// with another [`RenderStyle`]. Styles are interpreted in order of nesting.
+    /// Colors replace any previous color of the same kind, attributes are set or cleared,
+    /// and [`ANSISequence::Reset`] returns to [`RenderStyle::PLAIN`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use overture::ioopts::ansi::ANSISequence;
+    /// use overture::interfaces::styling::{Attributes, RenderStyle};
+    ///
+    /// let style = RenderStyle::PLAIN.with(ANSISequence::Bold).with(ANSISequence::NoBold);
+    /// assert!(style.is_plain());
+    /// ```
+    pub fn with(mut self, seq: ANSISequence) -> Self {
+        use ANSISequence as S;
+
+        match seq {
+            S::Reset => return RenderStyle::PLAIN,
+            S::Bold => self.attrs.insert(Attributes::BOLD),
+            S::Dim => self.attrs.insert(Attributes::DIM),
+            S::Italic => self.attrs.insert(Attributes::ITALIC),
+            S::Underline => self.attrs.insert(Attributes::UNDERLINE),
+            S::Blink => self.attrs.insert(Attributes::BLINK),
+            S::Invert => self.attrs.insert(Attributes::INVERT),
+            S::Hidden => self.attrs.insert(Attributes::HIDDEN),
+            S::Strikethrough => self.attrs.insert(Attributes::STRIKETHROUGH),
+
+            S::NoBold => self.attrs.remove(Attributes::BOLD),
+            S::NoDim => self.attrs.remove(Attributes::DIM),
+            S::NoItalic => self.attrs.remove(Attributes::ITALIC),
+            S::NoUnderline => self.attrs.remove(Attributes::UNDERLINE),
+            S::NoBlink => self.attrs.remove(Attributes::BLINK),
+            S::NoInvert => self.attrs.remove(Attributes::INVERT),
+            S::NoHidden => self.attrs.remove(Attributes::HIDDEN),
+            S::NoStrikethrough => self.attrs.remove(Attributes::STRIKETHROUGH),
+
+            S::FgBlack => self.fg = Some(Color::Black),
+            S::FgRed => self.fg = Some(Color::Red),
+            S::FgGreen => self.fg = Some(Color::Green),
+            S::FgYellow => self.fg = Some(Color::Yellow),
+            S::FgBlue => self.fg = Some(Color::Blue),
+            S::FgMagenta => self.fg = Some(Color::Magenta),
+            S::FgCyan => self.fg = Some(Color::Cyan),
+            S::FgWhite => self.fg = Some(Color::White),
+            S::FgBrightBlack => self.fg = Some(Color::BrightBlack),
+            S::FgBrightRed => self.fg = Some(Color::BrightRed),
+            S::FgBrightGreen => self.fg = Some(Color::BrightGreen),
+            S::FgBrightYellow => self.fg = Some(Color::BrightYellow),
+            S::FgBrightBlue => self.fg = Some(Color::BrightBlue),
+            S::FgBrightMagenta => self.fg = Some(Color::BrightMagenta),
+            S::FgBrightCyan => self.fg = Some(Color::BrightCyan),
+            S::FgBrightWhite => self.fg = Some(Color::BrightWhite),
+
+            S::BgBlack => self.bg = Some(Color::Black),
+            S::BgRed => self.bg = Some(Color::Red),
+            S::BgGreen => self.bg = Some(Color::Green),
+            S::BgYellow => self.bg = Some(Color::Yellow),
+            S::BgBlue => self.bg = Some(Color::Blue),
+            S::BgMagenta => self.bg = Some(Color::Magenta),
+            S::BgCyan => self.bg = Some(Color::Cyan),
+            S::BgWhite => self.bg = Some(Color::White),
+            S::BgBrightBlack => self.bg = Some(Color::BrightBlack),
+            S::BgBrightRed => self.bg = Some(Color::BrightRed),
+            S::BgBrightGreen => self.bg = Some(Color::BrightGreen),
+            S::BgBrightYellow => self.bg = Some(Color::BrightYellow),
+            S::BgBrightBlue => self.bg = Some(Color::BrightBlue),
+            S::BgBrightMagenta => self.bg = Some(Color::BrightMagenta),
+            S::BgBrightCyan => self.bg = Some(Color::BrightCyan),
+            S::BgBrightWhite => self.bg = Some(Color::BrightWhite),
+
+            S::FgRGB(r, g, b) => self.fg = Some(Color::Rgb(r, g, b)),
+            S::BgRGB(r, g, b) => self.bg = Some(Color::Rgb(r, g, b)),
+            S::UnderlineRGB(r, g, b) => self.underline_color = Some(Color::Rgb(r, g, b)),
+        }
+        self
+    }
+
+    /// Returns the SGR parameters that set this style from a reset terminal state.
+    pub(crate) fn sgr_params(&self) -> Vec<String> {
+        let mut params: Vec<String> = self.attrs.sgr_params().map(|p| p.to_string()).collect();
+        if let Some(fg) = self.fg {
+            params.push(fg.sgr_params(30));
+        }
+        if let Some(bg) = self.bg {
+            params.push(bg.sgr_params(40));
+        }
+        if let Some(ul) = self.underline_color {
+            params.push(ul.underline_sgr_params());
+        }
+        params
+    }
+
+    /// Returns a single ANSI escape sequence applying every part of this style.
+    ///
+    /// Returns an empty string for plain (and nil) styles.
+    ///
+    /// # Example
+    /// ```rust
+    /// use overture::ioopts::ansi::ANSISequence;
+    /// use overture::style;
+    ///
+    /// let style = style![ANSISequence::Bold, ANSISequence::Underline, ANSISequence::FgCyan];
+    /// assert_eq!(style.to_esc_code(), "\x1b[1;4;36m");
+    /// ```
+    pub fn to_esc_code(&self) -> String {
+        let params = self.sgr_params();
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("\x1b[{}m", params.join(";"))
+        }
+    }
+
+    /// Returns the shortest ANSI escape sequence switching a terminal from style `from` to `self`.
+    ///
+    /// Only the differing parts are emitted (e.g. `\x1b[39m` to restore the default foreground),
+    /// unless a full reset followed by this style is shorter. Returns an empty string if both
+    /// styles are equal. Nil styles are treated as plain.
     ///
     /// # Example
     /// ```rust
     /// use overture::ioopts::ansi::ANSISequence;
     /// use overture::interfaces::styling::RenderStyle;
+    /// use overture::style;
     ///
-    /// let style = RenderStyle::Styled(ANSISequence::Bold,
-    ///     Box::new(RenderStyle::Styled(ANSISequence::FgRed,
-    ///         Box::new(RenderStyle::Plain)
-    ///     ))
-    /// );
+    /// let cyan = style![ANSISequence::FgCyan];
+    /// let bold_cyan = style![ANSISequence::FgCyan, ANSISequence::Bold];
+    /// assert_eq!(bold_cyan.transition_from(&cyan), "\x1b[1m");
+    /// assert_eq!(cyan.transition_from(&cyan), "");
+    /// assert_eq!(RenderStyle::PLAIN.transition_from(&bold_cyan), "\x1b[0m");
     /// ```
-    Styled(ANSISequence, Box<RenderStyle>),
+    pub fn transition_from(&self, from: &RenderStyle) -> String {
+        let (from, to) = (from.visible(), self.visible());
+        if from == to {
+            return String::new();
+        }
+
+        let mut delta: Vec<String> = to.attrs
+            .transition_params(from.attrs)
+            .into_iter()
+            .map(|p| p.to_string())
+            .collect();
+        if from.fg != to.fg {
+            delta.push(to.fg.map_or("39".to_string(), |c| c.sgr_params(30)));
+        }
+        if from.bg != to.bg {
+            delta.push(to.bg.map_or("49".to_string(), |c| c.sgr_params(40)));
+        }
+        if from.underline_color != to.underline_color {
+            delta.push(to.underline_color.map_or("59".to_string(), |c| c.underline_sgr_params()));
+        }
+
+        let mut reset = vec!["0".to_string()];
+        reset.extend(to.sgr_params());
+
+        let delta = delta.join(";");
+        let reset = reset.join(";");
+        format!("\x1b[{}m", if reset.len() <= delta.len() { reset } else { delta })
+    }
+
+    /// Returns this style with the nil marker cleared, i.e. how it affects terminal state.
+    fn visible(&self) -> RenderStyle {
+        RenderStyle { nil: false, ..*self }
+    }
+}
+
+impl From<ANSISequence> for RenderStyle {
+    /// Builds a style from a single sequence applied on top of [`RenderStyle::PLAIN`].
+    fn from(seq: ANSISequence) -> Self {
+        RenderStyle::PLAIN.with(seq)
+    }
+}
+
+impl FromIterator<ANSISequence> for RenderStyle {
+    /// Folds the sequences in order on top of [`RenderStyle::PLAIN`].
+    fn from_iter<I: IntoIterator<Item = ANSISequence>>(iter: I) -> Self {
+        iter.into_iter().fold(RenderStyle::PLAIN, RenderStyle::with)
+    }
+}
+
+impl fmt::Debug for RenderStyle {
+    /// Formats only the parts of the style that are set, e.g. `RenderStyle { fg: Cyan, attrs: BOLD }`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.nil {
+            return write!(f, "RenderStyle::NIL");
+        }
+        if self.is_plain() {
+            return write!(f, "RenderStyle::PLAIN");
+        }
+        let mut s = f.debug_struct("RenderStyle");
+        if let Some(fg) = &self.fg {
+            s.field("fg", fg);
+        }
+        if let Some(bg) = &self.bg {
+            s.field("bg", bg);
+        }
+        if let Some(ul) = &self.underline_color {
+            s.field("underline_color", ul);
+        }
+        if !self.attrs.is_empty() {
+            s.field("attrs", &self.attrs);
+        }
+        s.finish()
+    }
 }
 
 /// Trait for types that support terminal styling using [`RenderStyle`].
@@ -79,4 +531,56 @@ pub trait Stylable: Renderable + Sized {
     ///
     /// This method enables method chaining on renderable elements for styling purposes.
     fn style(&self, style_seq: RenderStyle) -> Vec<Pixel>;
-}
\ No newline at end of file
+
+    /// Styles every pixel with the style returned for it by `styler`, returning a styled clone.
+    ///
+    /// The returned style replaces the pixel's style; return `pixel.content.style` to keep it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use overture::prelude::*;
+    ///
+    /// // Every other column in bold
+    /// let striped = primitives::Text::new("stripes", DiscreteCoord::ORIGIN)
+    ///     .rasterize()
+    ///     .style_with(|pixel| if pixel.position.x % 2 == 0 {
+    ///         style![ANSISequence::Bold]
+    ///     } else {
+    ///         pixel.content.style
+    ///     });
+    /// assert_eq!(striped[2].content.style, style![ANSISequence::Bold]);
+    /// assert!(striped[3].content.style.is_plain());
+    /// ```
+    fn style_with<F: FnMut(&Pixel) -> RenderStyle>(&self, mut styler: F) -> Vec<Pixel> {
+        self.pixels()
+            .into_iter()
+            .map(|mut pixel| {
+                pixel.content.style = styler(&pixel);
+                pixel
+            })
+            .collect()
+    }
+
+    /// Colors the item with a [`Gradient`] across its bounding box, returning a styled clone.
+    ///
+    /// Only the foreground or background color of each pixel changes; the rest of its style
+    /// is kept, so a gradient can be combined with [`style`](Stylable::style) applied before.
+    ///
+    /// # Example
+    /// ```rust
+    /// use overture::prelude::*;
+    /// use overture::interfaces::gradient::{Gradient, GradientDirection};
+    ///
+    /// let gradient = Gradient::new([(255, 0, 0), (0, 0, 255)], GradientDirection::Horizontal);
+    /// let pixels = primitives::Text::new("abc", DiscreteCoord::new(4, 2))
+    ///     .rasterize()
+    ///     .style(style![ANSISequence::Bold])
+    ///     .gradient(&gradient);
+    /// assert_eq!(pixels[0].content.style, style![ANSISequence::Bold, ANSISequence::FgRGB(255, 0, 0)]);
+    /// assert_eq!(pixels[1].content.style, style![ANSISequence::Bold, ANSISequence::FgRGB(128, 0, 128)]);
+    /// assert_eq!(pixels[2].content.style, style![ANSISequence::Bold, ANSISequence::FgRGB(0, 0, 255)]);
+    /// ```
+    fn gradient(&self, gradient: &Gradient) -> Vec<Pixel> {
+        gradient.apply(self.pixels())
+    }
+}
@@ -48,6 +48,7 @@
 //! the `cursor` submodule or adding new variants to the `ANSISequence` enum.
 
 
+#[allow(dead_code)]
 pub(crate) mod styling {
     pub(crate) const RESET: &str = "\x1b[0m";
     pub(crate) const BOLD: &str = "\x1b[1m";
@@ -115,7 +116,12 @@ pub(crate) mod color {
     pub fn bg_rgb(r: u8, g: u8, b: u8) -> String {
         format!("\x1b[48;2;{};{};{}m", r, g, b)
     }
+
+    pub fn underline_rgb(r: u8, g: u8, b: u8) -> String {
+        format!("\x1b[58;2;{};{};{}m", r, g, b)
+    }
 }
+#[allow(dead_code)]
 pub(crate) mod cursor {
     pub(crate) fn move_up(n: usize) -> String {
         format!("\x1b[{}A", n)
@@ -156,6 +162,12 @@ pub(crate) mod cursor {
     pub(crate) const DISABLE_ALTERNATE_SCREEN: &str = "\x1b[?1049l";
 }
 
+pub(crate) mod mouse {
+    /// Reports presses, releases, wheel and drags (1000 + 1002) in the SGR extended format (1006).
+    pub(crate) const ENABLE_SGR_MOUSE: &str = "\x1b[?1000h\x1b[?1002h\x1b[?1006h";
+    pub(crate) const DISABLE_SGR_MOUSE: &str = "\x1b[?1006l\x1b[?1002l\x1b[?1000l";
+}
+
 
 /// An enumeration of ANSI escape sequences used for styling terminal output.
 ///
@@ -314,6 +326,17 @@ pub enum ANSISequence {
     /// ```
     BgRGB(u8, u8, u8),
 
+    /// Set underline color to a custom RGB value using 24-bit color.
+    ///
+    /// Only affects underlined text, on terminals supporting colored underlines (SGR 58).
+    ///
+    /// # Example
+    /// ```rust
+    /// use overture::ioopts::ansi::ANSISequence;
+    /// 
+    /// let custom_ul = ANSISequence::UnderlineRGB(255, 0, 0);
+    /// ```
+    UnderlineRGB(u8, u8, u8),
 }
 
 impl ANSISequence {
@@ -401,6 +424,7 @@ impl ANSISequence {
             // RGB Colors (dereference tuple fields)
             ANSISequence::FgRGB(r, g, b) => color::fg_rgb(*r, *g, *b),
             ANSISequence::BgRGB(r, g, b) => color::bg_rgb(*r, *g, *b),
+            ANSISequence::UnderlineRGB(r, g, b) => color::underline_rgb(*r, *g, *b),
         }
     }
 }
@@ -13,6 +13,7 @@
 //! construct tables, frames, or other box-based UI elements in terminal applications.
 
 /// Provides Unicode box-drawing characters for constructing text-based boxes and tables.
+#[allow(clippy::module_inception)]
 pub mod box_drawing {
     // Corners
 
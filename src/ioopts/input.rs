@@ -0,0 +1,713 @@
+// Created by Sean L. on Jun. 25.
+// Last Updated by Sean L. on Jun. 25.
+//
+// overture.rs
+// src/ioopts/input.rs
+//
+// Makabaka1880, 2025. All rights reserved.
+
+//! Keyboard and Mouse Input
+//!
+//! This module turns the raw bytes a terminal sends on standard input into typed [`Event`]s.
+//!
+//! # Overview
+//!
+//! - [`InputDecoder`]: A byte-level decoder for keys, escape sequences (arrows, function keys,
+//!   Home/End/PageUp/PageDown, ...) with their modifiers, control characters, UTF-8 text and
+//!   SGR mouse reports. It does no IO and can be fed bytes from anywhere.
+//! - [`Input`] (Unix only): Puts the tty into raw mode and reads events from standard input,
+//!   either by polling with a timeout or as a blocking iterator. Raw mode is left when it is dropped.
+//!   Terminal resizes are reported as [`Event::Resize`].
+//!
+//! Mouse events are only reported while a [`MouseCapture`](crate::ioopts::terminal::MouseCapture)
+//! is alive. Their positions are zero-based cell coordinates, the same as the render engine's buffer.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use overture::prelude::*;
+//! use overture::ioopts::input::{Event, Input, KeyCode, KeyEvent, KeyModifiers};
+//!
+//! let mut engine = OvertureRenderEngine::new(80, 24);
+//! let mut input = Input::open().unwrap();
+//!
+//! loop {
+//!     engine.render(24).unwrap();
+//!     match input.poll(Duration::from_millis(50)).unwrap() {
+//!         Some(Event::Key(KeyEvent { code: KeyCode::Char('q'), .. })) => break,
+//!         Some(Event::Key(key)) if key == KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CTRL) => break,
+//!         Some(Event::Key(key)) => {
+//!             engine.load_renderable(primitives::Text::new(&format!("{:?}", key), DiscreteCoord::ORIGIN), None);
+//!         }
+//!         _ => {}
+//!     }
+//! }
+//! ```
+
+use std::fmt;
+use std::ops::{BitOr, BitOrAssign};
+
+use crate::interfaces::geometry::DiscreteCoord;
+
+#[cfg(unix)]
+use std::{collections::VecDeque, io, time::{Duration, Instant}};
+#[cfg(unix)]
+use crate::ioopts::terminal::{self, RawMode};
+
+/// A key, independent of the modifiers held while pressing it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyCode {
+    /// A text character. Letters typed with `Ctrl` are reported lowercase with [`KeyModifiers::CTRL`].
+    Char(char),
+    /// The Enter / Return key.
+    Enter,
+    /// The Tab key.
+    Tab,
+    /// Shift + Tab, as reported by most terminals.
+    BackTab,
+    /// The Backspace key.
+    Backspace,
+    /// The Escape key.
+    Esc,
+    /// The up arrow key.
+    Up,
+    /// The down arrow key.
+    Down,
+    /// The left arrow key.
+    Left,
+    /// The right arrow key.
+    Right,
+    /// The Home key.
+    Home,
+    /// The End key.
+    End,
+    /// The Page Up key.
+    PageUp,
+    /// The Page Down key.
+    PageDown,
+    /// The Insert key.
+    Insert,
+    /// The Delete key.
+    Delete,
+    /// A function key, from `F(1)` to `F(12)`.
+    F(u8),
+}
+
+/// A compact bitset of keyboard modifiers.
+///
+/// # Example
+/// ```rust
+/// use overture::ioopts::input::KeyModifiers;
+///
+/// let mods = KeyModifiers::CTRL | KeyModifiers::ALT;
+/// assert!(mods.contains(KeyModifiers::CTRL));
+/// assert!(!mods.contains(KeyModifiers::SHIFT));
+/// assert_eq!(format!("{:?}", mods), "CTRL | ALT");
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct KeyModifiers(u8);
+
+impl KeyModifiers {
+    /// No modifier held.
+    pub const NONE: KeyModifiers = KeyModifiers(0);
+    /// The Shift key.
+    pub const SHIFT: KeyModifiers = KeyModifiers(1 << 0);
+    /// The Alt (Meta) key.
+    pub const ALT: KeyModifiers = KeyModifiers(1 << 1);
+    /// The Control key.
+    pub const CTRL: KeyModifiers = KeyModifiers(1 << 2);
+
+    /// Every modifier paired with its name.
+    const TABLE: [(KeyModifiers, &'static str); 3] = [
+        (KeyModifiers::SHIFT, "SHIFT"),
+        (KeyModifiers::CTRL, "CTRL"),
+        (KeyModifiers::ALT, "ALT"),
+    ];
+
+    /// Returns `true` if every modifier in `other` is also set in `self`.
+    pub fn contains(&self, other: KeyModifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if no modifier is set.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Decodes the modifier parameter of a CSI sequence (`1 + bitmask`, e.g. `5` for `Ctrl`).
+    fn from_csi_param(param: u16) -> KeyModifiers {
+        let bits = param.saturating_sub(1);
+        let mut mods = KeyModifiers::NONE;
+        if bits & 1 != 0 {
+            mods |= KeyModifiers::SHIFT;
+        }
+        if bits & 2 != 0 {
+            mods |= KeyModifiers::ALT;
+        }
+        if bits & 4 != 0 {
+            mods |= KeyModifiers::CTRL;
+        }
+        mods
+    }
+}
+
+impl BitOr for KeyModifiers {
+    type Output = KeyModifiers;
+
+    /// Returns the union of two modifier sets.
+    fn bitor(self, rhs: Self) -> Self::Output {
+        KeyModifiers(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for KeyModifiers {
+    /// Adds the modifiers of `rhs` to `self` in-place.
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl fmt::Debug for KeyModifiers {
+    /// Formats the set as `CTRL | ALT`, or `NONE` if empty.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "NONE");
+        }
+        let names: Vec<&str> = Self::TABLE
+            .iter()
+            .filter(|(m, _)| self.contains(*m))
+            .map(|(_, n)| *n)
+            .collect();
+        write!(f, "{}", names.join(" | "))
+    }
+}
+
+/// A key press together with the modifiers held.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyEvent {
+    /// The key pressed.
+    pub code: KeyCode,
+    /// The modifiers held while pressing it.
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyEvent {
+    /// Creates a new key event.
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        KeyEvent { code, modifiers }
+    }
+
+    /// Creates a key event with no modifiers held.
+    pub fn plain(code: KeyCode) -> Self {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    /// Returns this event with the modifiers in `mods` added.
+    fn with(mut self, mods: KeyModifiers) -> Self {
+        self.modifiers |= mods;
+        self
+    }
+}
+
+/// A mouse button.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    /// The primary (usually left) button.
+    Left,
+    /// The middle button, often the wheel itself.
+    Middle,
+    /// The secondary (usually right) button.
+    Right,
+}
+
+/// What happened in a [`MouseEvent`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MouseEventKind {
+    /// A button was pressed.
+    Press(MouseButton),
+    /// A button was released.
+    Release(MouseButton),
+    /// The mouse moved while a button was held.
+    Drag(MouseButton),
+    /// The mouse moved with no button held. Only reported by terminals tracking all motion.
+    Moved,
+    /// The wheel was scrolled up.
+    ScrollUp,
+    /// The wheel was scrolled down.
+    ScrollDown,
+    /// The wheel was scrolled left (tilted).
+    ScrollLeft,
+    /// The wheel was scrolled right (tilted).
+    ScrollRight,
+}
+
+/// A mouse report together with the cell it happened on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MouseEvent {
+    /// What happened.
+    pub kind: MouseEventKind,
+    /// The zero-based cell, matching the render engine's buffer coordinates.
+    pub position: DiscreteCoord,
+    /// The modifiers held. Terminals often intercept some combinations, e.g. `Shift` for selection.
+    pub modifiers: KeyModifiers,
+}
+
+/// An input event read from the terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// A key was pressed.
+    Key(KeyEvent),
+    /// The mouse was used. Requires a [`MouseCapture`](crate::ioopts::terminal::MouseCapture).
+    Mouse(MouseEvent),
+    /// The terminal was resized to the given (columns, rows).
+    Resize(DiscreteCoord),
+}
+
+/// The result of decoding the start of the pending bytes.
+enum Decoded {
+    /// An event, and the number of bytes it was decoded from.
+    Event(Event, usize),
+    /// A number of bytes that do not form a known event.
+    Skip(usize),
+    /// The bytes are the start of a longer sequence.
+    Incomplete,
+}
+
+/// A byte-level decoder turning terminal input into [`Event`]s.
+///
+/// Bytes can arrive in arbitrary chunks; sequences split across chunks are kept until they are
+/// complete. Since a lone `Esc` byte is also the start of every escape sequence, it is only
+/// reported once the caller signals that no more bytes followed in time, through
+/// [`InputDecoder::timeout`].
+///
+/// # Examples
+///
+/// ```rust
+/// use overture::ioopts::input::{Event, InputDecoder, KeyCode, KeyEvent, KeyModifiers};
+///
+/// let mut decoder = InputDecoder::new();
+/// assert_eq!(
+///     decoder.feed(b"h\x1b[1;5A"),
+///     vec![
+///         Event::Key(KeyEvent::plain(KeyCode::Char('h'))),
+///         Event::Key(KeyEvent::new(KeyCode::Up, KeyModifiers::CTRL)),
+///     ]
+/// );
+///
+/// assert!(decoder.feed(b"\x1b").is_empty());
+/// assert_eq!(decoder.timeout(), vec![Event::Key(KeyEvent::plain(KeyCode::Esc))]);
+/// ```
+#[derive(Debug, Default)]
+pub struct InputDecoder {
+    pending: Vec<u8>,
+}
+
+impl InputDecoder {
+    /// Creates a decoder with no pending bytes.
+    pub fn new() -> Self {
+        InputDecoder::default()
+    }
+
+    /// Returns `true` if bytes of an incomplete sequence are waiting for more input.
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Appends `bytes` to the pending input and returns every event that is now complete.
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<Event> {
+        self.pending.extend_from_slice(bytes);
+        self.drain(false)
+    }
+
+    /// Decodes the pending bytes assuming no more input follows them.
+    ///
+    /// A lone `Esc` becomes [`KeyCode::Esc`], and an escape sequence cut short becomes the
+    /// `Alt`-modified key of its second byte.
+    pub fn timeout(&mut self) -> Vec<Event> {
+        self.drain(true)
+    }
+
+    fn drain(&mut self, complete: bool) -> Vec<Event> {
+        let mut events = vec![];
+        let mut start = 0;
+        while start < self.pending.len() {
+            match decode(&self.pending[start..], complete) {
+                Decoded::Event(event, len) => {
+                    events.push(event);
+                    start += len;
+                }
+                Decoded::Skip(len) => start += len,
+                Decoded::Incomplete => break,
+            }
+        }
+        self.pending.drain(..start);
+        events
+    }
+}
+
+/// Decodes the event at the start of `bytes`. If `complete` is set, no more bytes will follow.
+fn decode(bytes: &[u8], complete: bool) -> Decoded {
+    match bytes[0] {
+        0x1b => decode_escape(bytes, complete),
+        _ => decode_plain(bytes, complete),
+    }
+}
+
+/// Decodes a key that does not start with `Esc`: control characters and UTF-8 text.
+fn decode_plain(bytes: &[u8], complete: bool) -> Decoded {
+    let key = |code| Decoded::Event(Event::Key(KeyEvent::plain(code)), 1);
+    let ctrl = |c| Decoded::Event(Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::CTRL)), 1);
+    match bytes[0] {
+        b'\r' | b'\n' => key(KeyCode::Enter),
+        b'\t' => key(KeyCode::Tab),
+        0x7f | 0x08 => key(KeyCode::Backspace),
+        0x00 => ctrl(' '),
+        b @ 0x01..=0x1a => ctrl((b'a' + b - 1) as char),
+        b @ 0x1c..=0x1f => ctrl((b'\\' + b - 0x1c) as char),
+        b => {
+            let len = match b {
+                0x00..=0x7f => 1,
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => return Decoded::Skip(1),
+            };
+            if bytes.len() < len {
+                return if complete { Decoded::Skip(bytes.len()) } else { Decoded::Incomplete };
+            }
+            match std::str::from_utf8(&bytes[..len]).ok().and_then(|s| s.chars().next()) {
+                Some(c) => Decoded::Event(Event::Key(KeyEvent::plain(KeyCode::Char(c))), len),
+                None => Decoded::Skip(1),
+            }
+        }
+    }
+}
+
+/// Decodes a sequence starting with `Esc`: CSI and SS3 sequences, `Alt`-modified keys or `Esc` itself.
+fn decode_escape(bytes: &[u8], complete: bool) -> Decoded {
+    let esc = Decoded::Event(Event::Key(KeyEvent::plain(KeyCode::Esc)), 1);
+    let Some(&next) = bytes.get(1) else {
+        return if complete { esc } else { Decoded::Incomplete };
+    };
+
+    let sequence = match next {
+        b'[' => decode_csi(bytes),
+        b'O' => decode_ss3(bytes),
+        _ => None,
+    };
+    match sequence {
+        Some(Decoded::Incomplete) if complete => alt(&bytes[1..], complete),
+        Some(decoded) => decoded,
+        None => alt(&bytes[1..], complete),
+    }
+}
+
+/// Decodes the key following an `Esc` and adds the `Alt` modifier to it.
+fn alt(bytes: &[u8], complete: bool) -> Decoded {
+    match decode(bytes, complete) {
+        Decoded::Event(Event::Key(key), len) => {
+            Decoded::Event(Event::Key(key.with(KeyModifiers::ALT)), len + 1)
+        }
+        // Only keys can be escaped; anything else is noise
+        Decoded::Event(_, len) | Decoded::Skip(len) => Decoded::Skip(len + 1),
+        Decoded::Incomplete => Decoded::Incomplete,
+    }
+}
+
+/// Decodes a CSI sequence (`Esc [ params final`), or returns `Incomplete` if it is cut short.
+fn decode_csi(bytes: &[u8]) -> Option<Decoded> {
+    let body = &bytes[2..];
+    let Some(end) = body.iter().position(|b| (0x40..=0x7e).contains(b)) else {
+        return Some(Decoded::Incomplete);
+    };
+    let len = end + 3;
+    if body[0] == b'<' {
+        return Some(decode_sgr_mouse(&body[1..end], body[end], len));
+    }
+    let params: Vec<u16> = std::str::from_utf8(&body[..end])
+        .unwrap_or("")
+        .split(';')
+        .map(|p| p.parse().unwrap_or(0))
+        .collect();
+    let modifiers = KeyModifiers::from_csi_param(params.get(1).copied().unwrap_or(1));
+
+    let code = match body[end] {
+        b'A' => KeyCode::Up,
+        b'B' => KeyCode::Down,
+        b'C' => KeyCode::Right,
+        b'D' => KeyCode::Left,
+        b'H' => KeyCode::Home,
+        b'F' => KeyCode::End,
+        b'Z' => KeyCode::BackTab,
+        b @ b'P'..=b'S' => KeyCode::F(b - b'P' + 1),
+        b'~' => match params[0] {
+            1 | 7 => KeyCode::Home,
+            2 => KeyCode::Insert,
+            3 => KeyCode::Delete,
+            4 | 8 => KeyCode::End,
+            5 => KeyCode::PageUp,
+            6 => KeyCode::PageDown,
+            n @ 11..=15 => KeyCode::F((n - 10) as u8),
+            n @ 17..=21 => KeyCode::F((n - 11) as u8),
+            n @ 23..=24 => KeyCode::F((n - 12) as u8),
+            _ => return Some(Decoded::Skip(len)),
+        },
+        _ => return Some(Decoded::Skip(len)),
+    };
+    Some(Decoded::Event(Event::Key(KeyEvent::new(code, modifiers)), len))
+}
+
+/// Decodes the parameters and final byte of an SGR mouse report (`Esc [ < button ; x ; y M|m`).
+///
+/// `M` ends presses, drags and wheel events, `m` ends releases. The terminal's one-based
+/// coordinates are converted to zero-based cells.
+fn decode_sgr_mouse(params: &[u8], last: u8, len: usize) -> Decoded {
+    let params: Vec<u32> = std::str::from_utf8(params)
+        .unwrap_or("")
+        .split(';')
+        .filter_map(|p| p.parse().ok())
+        .collect();
+    let (code, x, y) = match (params.as_slice(), last) {
+        ([code, x, y], b'M' | b'm') => (*code, *x, *y),
+        _ => return Decoded::Skip(len),
+    };
+
+    let mut modifiers = KeyModifiers::NONE;
+    if code & 4 != 0 {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    if code & 8 != 0 {
+        modifiers |= KeyModifiers::ALT;
+    }
+    if code & 16 != 0 {
+        modifiers |= KeyModifiers::CTRL;
+    }
+
+    let button = match code & 3 {
+        0 => Some(MouseButton::Left),
+        1 => Some(MouseButton::Middle),
+        2 => Some(MouseButton::Right),
+        _ => None,
+    };
+    let kind = match (code & 64 != 0, code & 32 != 0, button) {
+        (true, _, _) => match code & 3 {
+            0 => MouseEventKind::ScrollUp,
+            1 => MouseEventKind::ScrollDown,
+            2 => MouseEventKind::ScrollLeft,
+            _ => MouseEventKind::ScrollRight,
+        },
+        (false, true, Some(button)) => MouseEventKind::Drag(button),
+        (false, true, None) => MouseEventKind::Moved,
+        (false, false, Some(button)) if last == b'm' => MouseEventKind::Release(button),
+        (false, false, Some(button)) => MouseEventKind::Press(button),
+        (false, false, None) => return Decoded::Skip(len),
+    };
+
+    let position = DiscreteCoord::new(x.saturating_sub(1), y.saturating_sub(1));
+    Decoded::Event(Event::Mouse(MouseEvent { kind, position, modifiers }), len)
+}
+
+/// Decodes an SS3 sequence (`Esc O final`), sent for F1-F4 and, in application mode, arrows.
+fn decode_ss3(bytes: &[u8]) -> Option<Decoded> {
+    let Some(&last) = bytes.get(2) else {
+        return Some(Decoded::Incomplete);
+    };
+    let code = match last {
+        b'A' => KeyCode::Up,
+        b'B' => KeyCode::Down,
+        b'C' => KeyCode::Right,
+        b'D' => KeyCode::Left,
+        b'H' => KeyCode::Home,
+        b'F' => KeyCode::End,
+        b @ b'P'..=b'S' => KeyCode::F(b - b'P' + 1),
+        _ => return None,
+    };
+    Some(Decoded::Event(Event::Key(KeyEvent::plain(code)), 3))
+}
+
+/// Keyboard events read from standard input, with the tty in raw mode (Unix only).
+///
+/// Events can be polled with a timeout, which suits render loops, or read in a blocking
+/// fashion through the [`Iterator`] implementation. Raw mode is left when `Input` is dropped.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use overture::ioopts::input::{Event, Input, KeyCode};
+///
+/// let mut input = Input::open().unwrap();
+/// for event in &mut input {
+///     if let Event::Key(key) = event.unwrap() {
+///         if key.code == KeyCode::Esc {
+///             break;
+///         }
+///     }
+/// }
+/// ```
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct Input {
+    decoder: InputDecoder,
+    queue: VecDeque<Event>,
+    /// When the pending escape sequence is given up on, if one is pending.
+    escape_deadline: Option<Instant>,
+    _raw: RawMode,
+}
+
+#[cfg(unix)]
+impl Input {
+    /// How long to wait for the rest of an escape sequence before treating `Esc` as a key press.
+    pub const ESCAPE_TIMEOUT: Duration = Duration::from_millis(25);
+
+    /// Enables raw mode and starts reading from standard input and watching for resizes.
+    pub fn open() -> io::Result<Self> {
+        terminal::watch_resize()?;
+        Ok(Input {
+            decoder: InputDecoder::new(),
+            queue: VecDeque::new(),
+            escape_deadline: None,
+            _raw: RawMode::enable()?,
+        })
+    }
+
+    /// Waits up to `timeout` for an event.
+    ///
+    /// # Returns
+    ///
+    /// The next event, or `None` if none arrived in time.
+    pub fn poll(&mut self, timeout: Duration) -> io::Result<Option<Event>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            self.check_resize();
+            if let Some(event) = self.queue.pop_front() {
+                return Ok(Some(event));
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let wait = self.escape_wait().map_or(remaining, |escape| escape.min(remaining));
+            match wait_readable(Some(wait))? {
+                Readiness::Readable => self.read_available()?,
+                // A signal, e.g. a resize: keep waiting for whatever time is left
+                Readiness::Interrupted => {}
+                Readiness::TimedOut => {
+                    if !self.flush_expired_escape() && Instant::now() >= deadline {
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Blocks until the next event arrives.
+    pub fn read(&mut self) -> io::Result<Event> {
+        loop {
+            self.check_resize();
+            if let Some(event) = self.queue.pop_front() {
+                return Ok(event);
+            }
+            match wait_readable(self.escape_wait())? {
+                Readiness::Readable => self.read_available()?,
+                Readiness::Interrupted => {}
+                Readiness::TimedOut => {
+                    self.flush_expired_escape();
+                }
+            }
+        }
+    }
+
+    /// Returns how much longer to wait for the rest of a pending escape sequence, starting the
+    /// [`ESCAPE_TIMEOUT`](Input::ESCAPE_TIMEOUT) if needed, or `None` if nothing is pending.
+    fn escape_wait(&mut self) -> Option<Duration> {
+        if !self.decoder.has_pending() {
+            self.escape_deadline = None;
+            return None;
+        }
+        let deadline = *self.escape_deadline.get_or_insert_with(|| Instant::now() + Input::ESCAPE_TIMEOUT);
+        Some(deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Queues the pending escape sequence as typed keys if its timeout ran out.
+    ///
+    /// Returns `true` if it did.
+    fn flush_expired_escape(&mut self) -> bool {
+        if self.escape_deadline.is_none_or(|deadline| Instant::now() < deadline) {
+            return false;
+        }
+        self.escape_deadline = None;
+        self.queue.extend(self.decoder.timeout());
+        true
+    }
+
+    /// Queues a resize event if the terminal was resized.
+    fn check_resize(&mut self) {
+        if terminal::take_resize() {
+            if let Ok(size) = terminal::terminal_size() {
+                self.queue.push_back(Event::Resize(size));
+            }
+        }
+    }
+
+    /// Reads the bytes currently available on standard input into the decoder.
+    fn read_available(&mut self) -> io::Result<()> {
+        let mut buf = [0u8; 1024];
+        // SAFETY: `buf` is valid for writes of `buf.len()` bytes.
+        let n = unsafe { libc::read(libc::STDIN_FILENO, buf.as_mut_ptr().cast(), buf.len()) };
+        match n {
+            n if n > 0 => {
+                // The rest of a sequence arrived, or a new one started
+                self.escape_deadline = None;
+                self.queue.extend(self.decoder.feed(&buf[..n as usize]));
+                Ok(())
+            }
+            0 => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "standard input was closed")),
+            _ => retry_interrupted(io::Error::last_os_error()),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Iterator for Input {
+    type Item = io::Result<Event>;
+
+    /// Blocks until the next event arrives. Never returns `None`.
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.read())
+    }
+}
+
+/// What [`wait_readable`] stopped waiting for.
+#[cfg(unix)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Readiness {
+    /// Standard input has bytes to read.
+    Readable,
+    /// The timeout elapsed.
+    TimedOut,
+    /// A signal arrived before either, e.g. `SIGWINCH`, which is installed without `SA_RESTART`.
+    Interrupted,
+}
+
+/// Waits until standard input is readable, `timeout` elapses or a signal arrives. `None` waits
+/// forever.
+#[cfg(unix)]
+fn wait_readable(timeout: Option<Duration>) -> io::Result<Readiness> {
+    let mut fd = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+    // Rounded up, so that a timeout is never reported before it elapsed
+    let millis = timeout.map_or(-1, |t| t.as_micros().div_ceil(1000).min(i32::MAX as u128) as i32);
+    // SAFETY: `fd` points to exactly one valid `pollfd`.
+    match unsafe { libc::poll(&mut fd, 1, millis) } {
+        n if n > 0 => Ok(Readiness::Readable),
+        0 => Ok(Readiness::TimedOut),
+        _ => retry_interrupted(io::Error::last_os_error()).map(|_| Readiness::Interrupted),
+    }
+}
+
+/// Swallows `EINTR`, e.g. from a signal arriving while blocked, and forwards any other error.
+#[cfg(unix)]
+fn retry_interrupted(err: io::Error) -> io::Result<()> {
+    match err.kind() {
+        io::ErrorKind::Interrupted => Ok(()),
+        _ => Err(err),
+    }
+}
@@ -15,7 +15,11 @@
 //! # Submodules
 //! - [`ansi`]: Utilities for working with ANSI escape sequences (e.g., colors, styles).
 //! - [`box_drawing`]: Functions and constants for rendering box drawing symbols in terminal UIs.
+//! - [`input`]: Raw-mode keyboard input, decoded into typed key events.
+//! - [`terminal`]: Session guard taking over the terminal (alternate screen, hidden cursor) and restoring it.
 
 
 pub mod ansi;
-pub mod box_drawing;
\ No newline at end of file
+pub mod box_drawing;
+pub mod input;
+pub mod terminal;
\ No newline at end of file
@@ -0,0 +1,407 @@
+// Created by Sean L. on Jun. 25.
+// Last Updated by Sean L. on Jun. 25.
+//
+// overture.rs
+// src/ioopts/terminal.rs
+//
+// Makabaka1880, 2025. All rights reserved.
+
+//! Terminal Session Management
+//!
+//! Full-screen applications switch the terminal into a state that must be undone on exit:
+//! the alternate screen is active and the cursor is hidden. This module provides
+//! [`TerminalSession`], a guard that sets that state up when created and restores the
+//! terminal when dropped, including when the program panics.
+//!
+//! Interactive applications additionally need the tty in raw mode, so that key presses are
+//! delivered immediately and unechoed. [`RawMode`] is the matching guard on Unix; it is usually
+//! managed by [`Input`](crate::ioopts::input::Input).
+//! [`MouseCapture`] additionally asks the terminal to report mouse events, which are then
+//! decoded by [`Input`](crate::ioopts::input::Input) as well.
+//!
+//! Finally, [`terminal_size`] queries the size of the terminal, and [`watch_resize`] starts
+//! tracking size changes (`SIGWINCH`), which are picked up with [`take_resize`] or reported by
+//! [`Input`](crate::ioopts::input::Input) as [`Event::Resize`](crate::ioopts::input::Event::Resize).
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use overture::prelude::*;
+//! use overture::ioopts::terminal::TerminalSession;
+//!
+//! fn main() -> std::io::Result<()> {
+//!     let _session = TerminalSession::start()?;
+//!
+//!     let mut engine = OvertureRenderEngine::new(80, 24);
+//!     engine.load_renderable(primitives::Text::new("Hello", DiscreteCoord::ORIGIN), None);
+//!     engine.render(24)?;
+//!     Ok(())
+//! } // The terminal is restored here, or before the panic message if `render` panics
+//! ```
+
+use std::io::{self, Write};
+use std::panic;
+use std::sync::Once;
+#[cfg(unix)]
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::ioopts::ansi::{cursor, mouse, styling};
+#[cfg(unix)]
+use crate::interfaces::geometry::DiscreteCoord;
+
+/// Whether a session currently holds the terminal.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Whether mouse reporting is currently enabled.
+static MOUSE: AtomicBool = AtomicBool::new(false);
+
+/// Set by the `SIGWINCH` handler, cleared by [`take_resize`].
+#[cfg(unix)]
+static RESIZED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the `SIGWINCH` handler is installed.
+#[cfg(unix)]
+static WATCHING_RESIZE: AtomicBool = AtomicBool::new(false);
+
+/// Guards the one-time installation of the restoring panic hook.
+static PANIC_HOOK: Once = Once::new();
+
+/// The tty attributes from before raw mode was enabled, while it is.
+#[cfg(unix)]
+static SAVED_TTY: Mutex<Option<libc::termios>> = Mutex::new(None);
+
+/// A guard owning the terminal state of a full-screen session.
+///
+/// Starting a session enters the alternate screen, hides the cursor and clears the screen.
+/// The user's terminal is restored (styles reset, cursor shown, main screen back) when:
+///
+/// - the guard is dropped, or [`TerminalSession::restore`] is called;
+/// - the program panics. The restoring hook runs before the previously installed panic hook,
+///   so the panic message ends up on the main screen instead of being wiped with the alternate one.
+///
+/// Only one session may be active at a time. Restoring is idempotent.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use overture::ioopts::terminal::TerminalSession;
+///
+/// let session = TerminalSession::start().unwrap();
+/// assert!(TerminalSession::is_active());
+/// drop(session);
+/// assert!(!TerminalSession::is_active());
+/// ```
+#[derive(Debug)]
+pub struct TerminalSession {
+    _private: (),
+}
+
+impl TerminalSession {
+    /// Takes over the terminal on standard output.
+    ///
+    /// # Returns
+    ///
+    /// The session guard, or an error if writing to standard output fails or another session
+    /// is already active (`io::ErrorKind::AlreadyExists`).
+    pub fn start() -> io::Result<Self> {
+        if ACTIVE.swap(true, Ordering::SeqCst) {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, "a terminal session is already active"));
+        }
+        install_panic_hook();
+
+        let mut out = io::stdout().lock();
+        let entered = write!(
+            out,
+            "{}{}{}{}",
+            cursor::ENABLE_ALTERNATE_SCREEN,
+            cursor::HIDE_CURSOR,
+            cursor::CLEAR_SCREEN,
+            cursor::move_to(1, 1),
+        )
+        .and_then(|_| out.flush());
+        drop(out);
+
+        match entered {
+            Ok(()) => Ok(TerminalSession { _private: () }),
+            Err(e) => {
+                restore_terminal();
+                Err(e)
+            }
+        }
+    }
+
+    /// Returns `true` while a session holds the terminal.
+    pub fn is_active() -> bool {
+        ACTIVE.load(Ordering::SeqCst)
+    }
+
+    /// Restores the terminal right away, ending the session. Dropping the guard does the same.
+    pub fn restore(self) {
+        drop(self);
+    }
+}
+
+impl Drop for TerminalSession {
+    /// Restores the terminal to the state it was in before the session started.
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Undoes everything [`TerminalSession::start`] set up, if a session is active.
+fn restore_terminal() {
+    if !ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    let mut out = io::stdout().lock();
+    // Nothing sensible can be done if the terminal is gone
+    let _ = write!(
+        out,
+        "{}{}{}",
+        styling::RESET,
+        cursor::SHOW_CURSOR,
+        cursor::DISABLE_ALTERNATE_SCREEN,
+    );
+    let _ = out.flush();
+}
+
+/// A guard keeping the tty attached to standard input in raw mode (Unix only).
+///
+/// In raw mode, input is neither line-buffered nor echoed, and `Ctrl+C`, `Ctrl+Z` and
+/// flow control keys are delivered as bytes instead of being handled by the tty. Output
+/// post-processing is left on, so `\n` still starts a new line when rendering.
+///
+/// The original attributes are restored when the guard is dropped or the program panics.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use overture::ioopts::terminal::RawMode;
+///
+/// let raw = RawMode::enable().unwrap();
+/// // ... read keys ...
+/// drop(raw);
+/// ```
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct RawMode {
+    _private: (),
+}
+
+#[cfg(unix)]
+impl RawMode {
+    /// Switches the tty on standard input to raw mode.
+    ///
+    /// # Returns
+    ///
+    /// The guard, or an error if standard input is not a tty or raw mode is already enabled
+    /// (`io::ErrorKind::AlreadyExists`).
+    pub fn enable() -> io::Result<Self> {
+        let mut saved = SAVED_TTY.lock().unwrap_or_else(|e| e.into_inner());
+        if saved.is_some() {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, "raw mode is already enabled"));
+        }
+        install_panic_hook();
+
+        // SAFETY: `termios` is plain old data, and is fully initialized by `tcgetattr` before use.
+        let mut original: libc::termios = unsafe { std::mem::zeroed() };
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut original) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut raw = original;
+        raw.c_iflag &= !(libc::BRKINT | libc::ICRNL | libc::INPCK | libc::ISTRIP | libc::IXON);
+        raw.c_cflag |= libc::CS8;
+        raw.c_lflag &= !(libc::ECHO | libc::ICANON | libc::IEXTEN | libc::ISIG);
+        raw.c_cc[libc::VMIN] = 1;
+        raw.c_cc[libc::VTIME] = 0;
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &raw) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        *saved = Some(original);
+        Ok(RawMode { _private: () })
+    }
+
+    /// Returns `true` while raw mode is enabled.
+    pub fn is_enabled() -> bool {
+        SAVED_TTY.lock().map(|s| s.is_some()).unwrap_or(false)
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    /// Restores the tty attributes from before raw mode was enabled.
+    fn drop(&mut self) {
+        restore_tty();
+    }
+}
+
+/// Restores the tty attributes saved by [`RawMode::enable`], if raw mode is enabled.
+#[cfg(unix)]
+fn restore_tty() {
+    // The lock may be poisoned if a panic happened while it was held; the attributes are still valid.
+    let mut saved = SAVED_TTY.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(original) = saved.take() {
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &original);
+        }
+    }
+}
+
+/// A guard keeping xterm SGR mouse reporting (mode 1006) enabled.
+///
+/// While enabled, the terminal reports button presses and releases, drags and the scroll wheel
+/// instead of handling them itself (e.g. for text selection). The reports are decoded into
+/// [`Event::Mouse`](crate::ioopts::input::Event::Mouse) by [`Input`](crate::ioopts::input::Input).
+///
+/// Reporting is disabled again when the guard is dropped or the program panics.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use overture::ioopts::input::{Event, Input};
+/// use overture::ioopts::terminal::MouseCapture;
+///
+/// let mut input = Input::open().unwrap();
+/// let _mouse = MouseCapture::enable().unwrap();
+/// if let Ok(Event::Mouse(mouse)) = input.read() {
+///     println!("{:?} at {:?}", mouse.kind, mouse.position);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MouseCapture {
+    _private: (),
+}
+
+impl MouseCapture {
+    /// Enables mouse reporting on standard output.
+    ///
+    /// # Returns
+    ///
+    /// The guard, or an error if writing to standard output fails or mouse reporting is already
+    /// enabled (`io::ErrorKind::AlreadyExists`).
+    pub fn enable() -> io::Result<Self> {
+        if MOUSE.swap(true, Ordering::SeqCst) {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, "mouse reporting is already enabled"));
+        }
+        install_panic_hook();
+
+        let mut out = io::stdout().lock();
+        let enabled = write!(out, "{}", mouse::ENABLE_SGR_MOUSE).and_then(|_| out.flush());
+        drop(out);
+
+        match enabled {
+            Ok(()) => Ok(MouseCapture { _private: () }),
+            Err(e) => {
+                disable_mouse();
+                Err(e)
+            }
+        }
+    }
+
+    /// Returns `true` while mouse reporting is enabled.
+    pub fn is_enabled() -> bool {
+        MOUSE.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for MouseCapture {
+    /// Disables mouse reporting.
+    fn drop(&mut self) {
+        disable_mouse();
+    }
+}
+
+/// Disables mouse reporting, if enabled.
+fn disable_mouse() {
+    if !MOUSE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    let mut out = io::stdout().lock();
+    let _ = write!(out, "{}", mouse::DISABLE_SGR_MOUSE);
+    let _ = out.flush();
+}
+
+/// Returns the size of the terminal as (columns, rows) (Unix only).
+///
+/// The size is queried with the `TIOCGWINSZ` ioctl on standard output, falling back to
+/// standard input and standard error when output is redirected.
+///
+/// # Returns
+///
+/// The size, or an error if none of the standard streams is a terminal.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use overture::ioopts::terminal::terminal_size;
+///
+/// let size = terminal_size().unwrap();
+/// println!("{} columns, {} rows", size.x, size.y);
+/// ```
+#[cfg(unix)]
+pub fn terminal_size() -> io::Result<DiscreteCoord> {
+    for fd in [libc::STDOUT_FILENO, libc::STDIN_FILENO, libc::STDERR_FILENO] {
+        // SAFETY: `winsize` is plain old data, filled in by the ioctl on success.
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+        if unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) } == 0 && size.ws_col > 0 && size.ws_row > 0 {
+            return Ok(DiscreteCoord::new(size.ws_col as u32, size.ws_row as u32));
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::Unsupported, "no standard stream is a terminal"))
+}
+
+/// Starts tracking terminal resizes by installing a `SIGWINCH` handler (Unix only).
+///
+/// Calling this more than once is harmless. Blocking reads of standard input are interrupted
+/// by resizes, so that event loops can react to them right away.
+pub fn watch_resize() -> io::Result<()> {
+    #[cfg(unix)]
+    if !WATCHING_RESIZE.swap(true, Ordering::SeqCst) {
+        // SAFETY: `sigaction` is plain old data; the handler only touches an atomic.
+        let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+        action.sa_sigaction = on_resize as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        action.sa_flags = 0;
+        unsafe { libc::sigemptyset(&mut action.sa_mask) };
+        if unsafe { libc::sigaction(libc::SIGWINCH, &action, std::ptr::null_mut()) } != 0 {
+            WATCHING_RESIZE.store(false, Ordering::SeqCst);
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Returns `true` if the terminal was resized since the last call, and clears the flag.
+///
+/// Always `false` unless [`watch_resize`] was called.
+pub fn take_resize() -> bool {
+    #[cfg(unix)]
+    {
+        RESIZED.swap(false, Ordering::SeqCst)
+    }
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+#[cfg(unix)]
+extern "C" fn on_resize(_signal: libc::c_int) {
+    RESIZED.store(true, Ordering::SeqCst);
+}
+
+/// Chains a hook restoring the terminal in front of the current panic hook.
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            #[cfg(unix)]
+            restore_tty();
+            disable_mouse();
+            restore_terminal();
+            previous(info);
+        }));
+    });
+}
@@ -1,5 +1,5 @@
 // Created by Sean L. on Jun. 23.
-// Last Updated by Sean L. on Jun. 23.
+// Last Updated by Sean L. on Jun. 25.
 // 
 // overture.rs
 // src/lib.rs
@@ -17,6 +17,7 @@
 //!
 //! - **Composable rendering engine** via the `Renderable` trait.
 //! - **Structured ANSI styling** with type-safe escape sequences.
+//! - **Gradients and per-pixel styling** for colorful text and ASCII art.
 //! - **Protected pixels** to prevent important UI elements from being pruned.
 //! - **Flexible layout placement**, including alignment presets and coordinate offsets.
 //! - **Modern design patterns** inspired by SwiftUI, TUI toolkits, and declarative UI systems.
@@ -24,19 +25,27 @@
 //! ## 📦 Modules
 //!
 //! - [`engine`] — The core rendering engine (`OvertureRenderEngine`).
+//! - [`app`] — Application runner: frame loop, input dispatch and terminal setup (Unix only).
 //! - [`interfaces`] — Traits, data structures, and abstraction interfaces.
 //! - [`ioopts`] — ANSI terminal options: styling, cursor control, etc.
 //! - [`primitives`] — UI shapes, boxes, and composition-ready widgets.
+//! - [`error`] — The crate error type, `OvertureError`.
 //! - [`prelude`] — Common types and traits for quick use.
+//! - `testing` — Headless engine and snapshot assertions (requires the `testing` feature).
+//!
+//! ## ⚙️ Features
+//!
+//! - `all-fonts` (default) — Embeds every bundled font. Enable single fonts with `font-3-d`, `font-3x5`, `font-acrobatic`, `font-alphabet`, `font-big`, `font-blocks` or `font-larry3d` instead. `blocks` is a TOIlet font of full blocks.
+//! - `testing` — Enables the `testing` module.
 //!
 //! ## 🔍 Example
 //!
 //! ```no_run
 //! use overture::prelude::*;
 //! 
-//! let cols = 100; let rows = 20;
-//! let mut engine = OvertureRenderEngine::new(cols, rows - 2);
-//! let screen_dim = DiscreteCoord::new(cols, rows);
+//! let mut engine = OvertureRenderEngine::fit_terminal().unwrap();
+//! let screen_dim = engine.size();
+//! let (cols, rows) = (screen_dim.x, screen_dim.y);
 //! 
 //! let banner = primitives::text::Text::new("Welcome To", DiscreteCoord::ORIGIN)
 //!     .rasterize()
@@ -61,7 +70,7 @@
 //! engine.load_renderable(banner, None);
 //! engine.load_renderable(brand, None);
 //! engine.load_renderable(box_frame, None);
-//! engine.render(rows as u16);
+//! engine.render(rows as u16).unwrap();
 //! ```
 //!
 //! This example creates a centered styled text banner and surrounds it with a soft box frame.
@@ -104,7 +113,14 @@
 pub mod interfaces;
 pub mod ioopts;
 pub mod engine;
+#[cfg(unix)]
+pub mod app;
 pub mod primitives;
+pub mod error;
 pub mod prelude;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub(crate) mod utils;
 
-pub(crate) mod utils;
\ No newline at end of file
+pub use error::{OvertureError, OvertureResult};
\ No newline at end of file
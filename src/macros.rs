@@ -18,11 +18,12 @@
 /// 
 /// - [`renderable_list!`]: Constructs a `RenderableList` from a sequence of renderable expressions,
 ///   expanding to a linked-list-like structure.
-/// - [`style!`]: Builds a recursive style chain using `RenderStyle::Styled`, with a fallback to `RenderStyle::Plain`.
+/// - [`style!`]: Builds a flat `RenderStyle` by applying ANSI sequences in order on top of `RenderStyle::PLAIN`.
 /// - [`some!`]: A convenience macro for wrapping a value in `Option::Some`.
 /// 
 /// These macros are designed to improve ergonomics and reduce boilerplate when working with
 /// renderable lists, style chains, and optional values in the overture ecosystem.
+#[allow(clippy::module_inception)]
 pub mod macros {
     /// Constructs a `RenderableList` using a list of renderable expressions.
     ///
@@ -35,8 +36,8 @@ pub mod macros {
     /// use overture::prelude::*;
     /// 
     /// let list = renderable_list![
-    ///     DummyRenderable,
-    ///     DummyRenderable
+    ///     primitives::Text::new("a", DiscreteCoord::ORIGIN),
+    ///     primitives::Text::new("b", DiscreteCoord::new(0, 1))
     /// ];
     ///
     /// match list {
@@ -57,33 +58,30 @@ pub mod macros {
         };
     }
 
-    /// Constructs a `RenderStyle` by chaining style elements.
+    /// Constructs a `RenderStyle` from a list of ANSI sequences.
     ///
-    /// This macro builds up a recursive style chain using
-    /// `RenderStyle::Styled`, with the final fallback being `RenderStyle::Plain`.
+    /// This macro folds each sequence in order on top of `RenderStyle::PLAIN`
+    /// using `RenderStyle::with`, so later sequences override earlier conflicting ones.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use overture::prelude::*;
+    /// use overture::interfaces::styling::{Attributes, Color};
     ///
-    /// let style_chain = style![ANSISequence::Bold, ANSISequence::Underline];
+    /// let style = style![ANSISequence::Bold, ANSISequence::FgRed, ANSISequence::FgGreen];
     ///
-    /// match style_chain {
-    ///     RenderStyle::Styled(_, _) => { /* success */ },
-    ///     _ => panic!("Expected styled chain"),
-    /// }
+    /// assert!(style.attrs.contains(Attributes::BOLD));
+    /// assert_eq!(style.fg, Some(Color::Green));
+    /// assert_eq!(style![], RenderStyle::PLAIN);
     /// ```
     #[macro_export]
     macro_rules! style {
         () => {
-            $crate::interfaces::styling::RenderStyle::Plain
+            $crate::interfaces::styling::RenderStyle::PLAIN
         };
-        ($head:expr $(, $tail:expr)* $(,)?) => {
-            $crate::interfaces::styling::RenderStyle::Styled(
-                $head,
-                Box::new(style![$($tail),*])
-            )
+        ($($seq:expr),+ $(,)?) => {
+            $crate::interfaces::styling::RenderStyle::PLAIN$(.with($seq))+
         };
     }
 
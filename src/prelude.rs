@@ -18,11 +18,16 @@ pub use crate::interfaces::containers::RenderableList;
 pub use crate::interfaces::pixels::Pixel;
 pub use crate::interfaces::styling::RenderStyle;
 pub use crate::interfaces::styling::Stylable;
+pub use crate::interfaces::layers::Maskable;
 
 pub use crate::ioopts::ansi::ANSISequence;
+pub use crate::ioopts::terminal::{MouseCapture, TerminalSession};
+pub use crate::ioopts::input::{Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+#[cfg(unix)]
+pub use crate::ioopts::input::Input;
 
 pub use crate::primitives;
 
-pub use crate::engine::OvertureRenderEngine;
+pub use crate::engine::{OvertureRenderEngine, RenderId};
 
 pub use crate::{style, renderable_list, some};
\ No newline at end of file
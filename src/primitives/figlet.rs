@@ -0,0 +1,605 @@
+// Created by Sean L. on Jun. 25.
+// Last Updated by Sean L. on Jun. 25.
+//
+// overture.rs
+// src/primitives/figlet.rs
+//
+// Makabaka1880, 2025. All rights reserved.
+
+//! FIGlet Horizontal Layout
+//!
+//! This module lays out FIGlet banners the way the `figlet` program does. Where `figlet_rs`
+//! places glyphs side by side at their full width, a [`FigletFont`] keeps the hardblanks and
+//! the layout settings of its font file, and can move glyphs together:
+//!
+//! - [`Layout::FullWidth`] keeps every glyph at its full width.
+//! - [`Layout::Fitting`] (kerning) moves glyphs together until they touch.
+//! - [`Layout::Smushing`] moves them one column further, merging the touching characters by
+//!   the enabled [`SmushRules`].
+//! - [`Layout::FontDefault`] uses the layout the font file asks for.
+//!
+//! [`FigletOptions`] combine a layout with a target width: banners wider than it are wrapped
+//! between words, or rendered in a narrower fallback font.
+//!
+//! # TOIlet Fonts
+//!
+//! TOIlet `.tlf` fonts share the format of FIGlet fonts, with a `tlf2a` signature, and are
+//! written in UTF-8, typically with Unicode block characters. They are parsed and laid out the
+//! same way. Their banners are plain text, so they become [`Text`](crate::primitives::Text)
+//! and pixels like any other banner, and can be styled as a whole, or glyph by glyph using
+//! [`FigletFont::glyph_spans`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use overture::primitives::figlet::{Layout, SmushRules};
+//! use overture::primitives::fonts;
+//!
+//! let font = fonts::try_figlet_font("standard").unwrap();
+//! let full = font.render("LT", Layout::FullWidth);
+//! let smushed = font.render("LT", Layout::Smushing(SmushRules::ALL));
+//! assert!(smushed[0].len() < full[0].len());
+//! ```
+
+use std::collections::HashMap;
+use std::ops::{BitOr, Range};
+
+use figlet_rs::{FIGcharacter, FIGfont, HeaderLine};
+
+use crate::interfaces::rendering::str_width;
+
+/// The rules by which [`Layout::Smushing`] merges two touching characters into one.
+///
+/// Rules combine with `|`. With no rule enabled ([`SmushRules::UNIVERSAL`]), the character of
+/// the right glyph always wins, except over hardblanks.
+///
+/// # Examples
+///
+/// ```rust
+/// use overture::primitives::figlet::SmushRules;
+///
+/// let rules = SmushRules::EQUAL_CHARACTER | SmushRules::UNDERSCORE;
+/// assert!(rules.contains(SmushRules::UNDERSCORE));
+/// assert!(!rules.contains(SmushRules::BIG_X));
+/// assert_eq!(rules.bits(), 3);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SmushRules {
+    bits: u8,
+}
+
+impl SmushRules {
+    /// No rules: universal smushing.
+    pub const UNIVERSAL: SmushRules = SmushRules { bits: 0 };
+    /// Two equal characters merge into one. Rule 1.
+    pub const EQUAL_CHARACTER: SmushRules = SmushRules { bits: 1 };
+    /// An underscore is replaced by any of `|/\[]{}()<>`. Rule 2.
+    pub const UNDERSCORE: SmushRules = SmushRules { bits: 2 };
+    /// Of the classes `|`, `/\`, `[]`, `{}`, `()` and `<>`, the later one wins. Rule 3.
+    pub const HIERARCHY: SmushRules = SmushRules { bits: 4 };
+    /// Opposite brackets, braces or parentheses merge into `|`. Rule 4.
+    pub const OPPOSITE_PAIR: SmushRules = SmushRules { bits: 8 };
+    /// `/\` merges into `|`, `\/` into `Y` and `><` into `X`. Rule 5.
+    pub const BIG_X: SmushRules = SmushRules { bits: 16 };
+    /// Two hardblanks merge into one. Rule 6.
+    pub const HARDBLANK: SmushRules = SmushRules { bits: 32 };
+    /// All six rules.
+    pub const ALL: SmushRules = SmushRules { bits: 63 };
+
+    /// Returns the rules whose numbers are set in `bits`, as in the layout fields of a
+    /// FIGlet font header. Bits above the sixth are ignored.
+    pub const fn from_bits(bits: u8) -> Self {
+        SmushRules { bits: bits & 63 }
+    }
+
+    /// Returns the rules as header bits.
+    pub const fn bits(self) -> u8 {
+        self.bits
+    }
+
+    /// Returns `true` if every rule in `other` is enabled.
+    pub const fn contains(self, other: SmushRules) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    /// Returns `true` if no rule is enabled.
+    pub const fn is_universal(self) -> bool {
+        self.bits == 0
+    }
+}
+
+impl BitOr for SmushRules {
+    type Output = SmushRules;
+
+    fn bitor(self, other: SmushRules) -> SmushRules {
+        SmushRules { bits: self.bits | other.bits }
+    }
+}
+
+/// How the glyphs of a FIGlet banner are placed next to each other.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Layout {
+    /// The layout the font file asks for. The default.
+    #[default]
+    FontDefault,
+    /// Every glyph keeps its full width.
+    FullWidth,
+    /// Glyphs move together until they touch. Also known as kerning.
+    Fitting,
+    /// Glyphs move together one column further than [`Layout::Fitting`], merging the
+    /// characters that overlap by the given rules. Glyphs one column wide are only fitted.
+    Smushing(SmushRules),
+}
+
+/// Settings for laying out a FIGlet banner, see
+/// [`Text::ascii_art_with`](crate::primitives::Text::ascii_art_with).
+///
+/// Configured with `with_*` methods.
+///
+/// # Examples
+///
+/// ```rust
+/// use overture::primitives::figlet::{FigletOptions, Layout};
+///
+/// let options = FigletOptions::new()
+///     .with_layout(Layout::Fitting)
+///     .with_width(40)
+///     .with_fallback_font("3x5");
+/// assert_eq!(options.width, Some(40));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FigletOptions {
+    /// How glyphs are placed next to each other.
+    pub layout: Layout,
+
+    /// The width banners must fit in, in columns, if any.
+    ///
+    /// A banner too wide for it is rendered in the first of the
+    /// [`fallback_fonts`](FigletOptions::fallback_fonts) it fits in. If it fits in none, its
+    /// words are wrapped onto several banners in the last font tried, and words too wide by
+    /// themselves are broken between characters.
+    pub width: Option<u32>,
+
+    /// Names of the fonts tried, in order, when a banner does not fit in
+    /// [`width`](FigletOptions::width). Fonts without a glyph for every character of the text
+    /// are skipped.
+    pub fallback_fonts: Vec<String>,
+}
+
+impl FigletOptions {
+    /// Creates options for the font's default layout, without a width limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns these options with the given [`Layout`].
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Returns these options fitting banners in `width` columns.
+    pub fn with_width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Returns these options with `font_name` appended to the fallback fonts.
+    pub fn with_fallback_font<S: Into<String>>(mut self, font_name: S) -> Self {
+        self.fallback_fonts.push(font_name.into());
+        self
+    }
+}
+
+/// A FIGlet or TOIlet font parsed for layout.
+///
+/// Unlike [`figlet_rs::FIGfont`], it keeps hardblanks, the blanks that must not be fitted or
+/// smushed away, and the default layout of the font file. Every font of the
+/// [font registry](crate::primitives::fonts) is parsed as a `FigletFont`, e.g. with
+/// [`try_figlet_font`](crate::primitives::fonts::try_figlet_font); its `FIGfont` is converted
+/// from it.
+#[derive(Clone, Debug)]
+pub struct FigletFont {
+    /// The header line of the font file.
+    header: String,
+    /// The comment lines of the font file, joined by line breaks.
+    comments: String,
+    hardblank: char,
+    height: usize,
+    layout: Layout,
+    /// The rows of every glyph, padded to the same width.
+    glyphs: HashMap<char, Vec<Vec<char>>>,
+}
+
+/// The Deutsch characters every FIGlet font defines after printable ASCII, before its
+/// code-tagged characters.
+const REQUIRED_CHARACTERS: [u32; 7] = [196, 214, 220, 228, 246, 252, 223];
+
+impl FigletFont {
+    /// Parses `flf`, the content of a FIGlet or TOIlet font file.
+    ///
+    /// Characters with negative code tags, which FIGlet reserves for characters that cannot
+    /// be typed, are skipped.
+    ///
+    /// # Returns
+    ///
+    /// The font, or a description of what is wrong with it.
+    pub(crate) fn parse(flf: &str) -> Result<Self, String> {
+        let mut lines = flf.lines();
+        let header = lines.next().ok_or("empty font file")?;
+        // Checked here, so that the font can always be converted to a `FIGfont`
+        HeaderLine::try_from(header)?;
+        let fields: Vec<&str> = header.split_whitespace().collect();
+        let signature = fields.first().copied().unwrap_or_default();
+        let hardblank = signature
+            .strip_prefix("flf2a")
+            .or_else(|| signature.strip_prefix("tlf2a"))
+            .and_then(|rest| rest.chars().next())
+            .ok_or_else(|| format!("bad signature {:?}", signature))?;
+        let number = |i: usize, field: &str| {
+            fields
+                .get(i)
+                .and_then(|n| n.parse::<i32>().ok())
+                .ok_or_else(|| format!("missing or bad {} in header", field))
+        };
+        let height = usize::try_from(number(1, "height")?).map_err(|_| "negative height")?;
+        let old_layout = number(4, "old layout")?;
+        let comments = usize::try_from(number(5, "comment lines")?).map_err(|_| "negative comment lines")?;
+        let full_layout = fields.get(7).and_then(|n| n.parse::<i32>().ok());
+
+        let comments: Vec<&str> = lines.by_ref().take(comments).collect();
+        let read_glyph = |lines: &mut dyn Iterator<Item = &str>| -> Option<Vec<Vec<char>>> {
+            let rows: Vec<Vec<char>> = lines.take(height).map(glyph_row).collect();
+            (rows.len() == height).then(|| pad(rows))
+        };
+
+        let mut glyphs = HashMap::new();
+        let required = (32..=126).chain(REQUIRED_CHARACTERS);
+        for code in required {
+            let Some(glyph) = read_glyph(&mut lines) else { break };
+            glyphs.extend(char::from_u32(code).map(|ch| (ch, glyph)));
+        }
+        while let Some(tag) = lines.next() {
+            let code = code_tag(tag).ok_or_else(|| format!("bad code tag {:?}", tag))?;
+            let glyph = read_glyph(&mut lines).ok_or_else(|| format!("incomplete character {:?}", tag))?;
+            glyphs.extend(code.and_then(char::from_u32).map(|ch| (ch, glyph)));
+        }
+
+        Ok(FigletFont {
+            header: header.to_string(),
+            comments: comments.join("\n"),
+            hardblank,
+            height,
+            layout: header_layout(old_layout, full_layout),
+            glyphs,
+        })
+    }
+
+    /// Returns the height of the font's glyphs, in rows.
+    pub fn height(&self) -> u32 {
+        self.height as u32
+    }
+
+    /// Returns the layout the font file asks for. Never [`Layout::FontDefault`].
+    pub fn default_layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Returns `true` if the font has a glyph for `ch`.
+    pub fn contains(&self, ch: char) -> bool {
+        self.glyphs.contains_key(&ch)
+    }
+
+    /// Renders `text` as a single banner, returning its rows.
+    ///
+    /// Characters without a glyph, including line breaks, are left out. Hardblanks become
+    /// spaces. An empty text gives [`height`](FigletFont::height) empty rows.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::primitives::figlet::Layout;
+    /// use overture::primitives::fonts;
+    ///
+    /// let font = fonts::try_figlet_font("standard").unwrap();
+    /// let rows = font.render("/\\", Layout::Fitting);
+    /// assert_eq!(rows.len(), 6);
+    /// assert_eq!(font.width("/\\", Layout::Fitting), rows[0].chars().count() as u32);
+    /// ```
+    pub fn render(&self, text: &str, layout: Layout) -> Vec<String> {
+        self.lay_out(text, layout).0
+    }
+
+    /// Returns the columns each glyph of `text` covers in its banner, as rendered by
+    /// [`render`](FigletFont::render), with the character it stands for.
+    ///
+    /// Characters without a glyph are left out. Where glyphs were fitted or smushed together,
+    /// their spans overlap.
+    ///
+    /// # Examples
+    ///
+    /// Styling every other glyph of a banner:
+    ///
+    /// ```rust
+    /// use overture::prelude::*;
+    /// use overture::primitives::figlet::Layout;
+    /// use overture::primitives::fonts;
+    ///
+    /// let font = fonts::try_figlet_font("blocks").unwrap();
+    /// let spans = font.glyph_spans("12:30", Layout::FontDefault);
+    /// assert_eq!(spans[2], (':', 7..9));
+    ///
+    /// let banner = primitives::Text::new(font.render("12:30", Layout::FontDefault).join("\n"), DiscreteCoord::ORIGIN);
+    /// let pixels: Vec<Pixel> = banner
+    ///     .pixels()
+    ///     .into_iter()
+    ///     .map(|mut pixel| {
+    ///         if spans.iter().step_by(2).any(|(_, span)| span.contains(&pixel.position.x)) {
+    ///             pixel.content.style = style![ANSISequence::FgCyan];
+    ///         }
+    ///         pixel
+    ///     })
+    ///     .collect();
+    /// ```
+    pub fn glyph_spans(&self, text: &str, layout: Layout) -> Vec<(char, Range<u32>)> {
+        self.lay_out(text, layout).1
+    }
+
+    /// Lays out `text` as a single banner, returning its rows and the spans of its glyphs.
+    fn lay_out(&self, text: &str, layout: Layout) -> (Vec<String>, Vec<(char, Range<u32>)>) {
+        let layout = match layout {
+            Layout::FontDefault => self.layout,
+            layout => layout,
+        };
+        let mut rows: Vec<Vec<char>> = vec![vec![]; self.height];
+        let mut spans = vec![];
+        let mut previous_width = 0;
+        for (ch, glyph) in text.chars().filter_map(|ch| Some((ch, self.glyphs.get(&ch)?))) {
+            let width = glyph.first().map_or(0, Vec::len);
+            let smusher = Smusher { hardblank: self.hardblank, layout, widths: (previous_width, width) };
+            let overlap = smusher.overlap(&rows, glyph);
+            for (row, glyph_row) in rows.iter_mut().zip(glyph) {
+                let length = row.len();
+                for (k, &right) in glyph_row[..overlap].iter().enumerate() {
+                    // Overlapping columns left of the banner's start are dropped
+                    if let Some(column) = (length + k).checked_sub(overlap) {
+                        row[column] = smusher.smush(row[column], right).unwrap_or(right);
+                    }
+                }
+                row.extend_from_slice(&glyph_row[overlap..]);
+            }
+            let end = rows.first().map_or(0, Vec::len);
+            spans.push((ch, end.saturating_sub(width) as u32..end as u32));
+            previous_width = width;
+        }
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(|ch| if ch == self.hardblank { ' ' } else { ch }).collect())
+            .collect();
+        (rows, spans)
+    }
+
+    /// Converts the font into a [`FIGfont`], for [`fonts::font`](crate::primitives::fonts::font)
+    /// and [`Text::ascii_art`](crate::primitives::Text::ascii_art).
+    ///
+    /// Hardblanks become spaces, as when `figlet_rs` parses a font itself.
+    pub(crate) fn to_figfont(&self) -> FIGfont {
+        let header_line = HeaderLine::try_from(self.header.as_str()).expect("header checked when parsing");
+        let fonts = self
+            .glyphs
+            .iter()
+            .map(|(&ch, glyph)| {
+                let characters: Vec<String> = glyph
+                    .iter()
+                    .map(|row| row.iter().map(|&c| if c == self.hardblank { ' ' } else { c }).collect())
+                    .collect();
+                let width = glyph.first().map_or(0, Vec::len) as u32;
+                let character = FIGcharacter { code: ch as u32, characters, width, height: self.height as u32 };
+                (ch as u32, character)
+            })
+            .collect();
+        FIGfont { header_line, comments: self.comments.clone(), fonts }
+    }
+
+    /// Returns the width of `text` rendered as a banner, in columns.
+    pub fn width(&self, text: &str, layout: Layout) -> u32 {
+        banner_width(&self.render(text, layout))
+    }
+
+    /// Renders `text` as banners no wider than `width`, breaking it between words, or between
+    /// characters where a word does not fit by itself. Returns the rows of all banners.
+    pub(crate) fn render_wrapped(&self, text: &str, layout: Layout, width: u32) -> Vec<String> {
+        let fits = |text: &str| self.width(text, layout) <= width;
+        let mut lines: Vec<String> = vec![];
+        let mut line = String::new();
+        for word in text.split_whitespace() {
+            let joined = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+            if fits(&joined) {
+                line = joined;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            for ch in word.chars() {
+                line.push(ch);
+                if !fits(&line) && line.chars().count() > 1 {
+                    line.pop();
+                    lines.push(std::mem::replace(&mut line, ch.to_string()));
+                }
+            }
+        }
+        if !line.is_empty() || lines.is_empty() {
+            lines.push(line);
+        }
+        lines.iter().flat_map(|line| self.render(line, layout)).collect()
+    }
+}
+
+/// Renders the lines of a text as stacked banners, fitting them in `options.width`.
+///
+/// `fonts` holds the font asked for, followed by the usable fallback fonts. The first font
+/// all lines fit in is used; if there is none, the lines are wrapped in the last one.
+pub(crate) fn render_fitted(lines: &[&str], fonts: &[&FigletFont], options: &FigletOptions) -> Vec<String> {
+    let render = |font: &FigletFont| -> Vec<String> {
+        lines.iter().flat_map(|line| font.render(line, options.layout)).collect()
+    };
+    let (Some(width), Some(last)) = (options.width, fonts.last()) else {
+        return fonts.first().map(|font| render(font)).unwrap_or_default();
+    };
+    for font in fonts {
+        let rows = render(font);
+        if banner_width(&rows) <= width {
+            return rows;
+        }
+    }
+    lines.iter().flat_map(|line| last.render_wrapped(line, options.layout, width)).collect()
+}
+
+/// Returns the display width of the widest row of a banner.
+fn banner_width(rows: &[String]) -> u32 {
+    rows.iter().map(|row| str_width(row)).max().unwrap_or(0)
+}
+
+/// Merges the characters where two glyphs overlap, following the `figlet` program.
+struct Smusher {
+    hardblank: char,
+    layout: Layout,
+    /// The widths of the glyph on the left and of the one on the right.
+    widths: (usize, usize),
+}
+
+impl Smusher {
+    /// Returns how many columns `glyph` may overlap the end of the banner `rows`.
+    fn overlap(&self, rows: &[Vec<char>], glyph: &[Vec<char>]) -> usize {
+        if self.layout == Layout::FullWidth {
+            return 0;
+        }
+        let mut overlap = self.widths.1;
+        for (row, glyph_row) in rows.iter().zip(glyph) {
+            // The last visible character of the banner and the first one of the glyph
+            let (end, left) = match row.iter().rposition(|&ch| ch != ' ') {
+                Some(end) => (end, Some(row[end])),
+                None => (0, None),
+            };
+            let start = glyph_row.iter().position(|&ch| ch != ' ').unwrap_or(glyph_row.len());
+            let mut amount = start + row.len() - end;
+            if let Some(left) = left {
+                if glyph_row.get(start).is_none_or(|&right| self.smush(left, right).is_none()) {
+                    amount -= 1;
+                }
+            }
+            overlap = overlap.min(amount);
+        }
+        overlap
+    }
+
+    /// Returns the character `left` and `right` merge into, or `None` if they do not.
+    fn smush(&self, left: char, right: char) -> Option<char> {
+        if left == ' ' {
+            return Some(right);
+        }
+        if right == ' ' {
+            return Some(left);
+        }
+        if self.widths.0 < 2 || self.widths.1 < 2 {
+            return None;
+        }
+        let Layout::Smushing(rules) = self.layout else { return None };
+
+        let hardblank = self.hardblank;
+        if rules.is_universal() {
+            return Some(if right == hardblank { left } else { right });
+        }
+        if rules.contains(SmushRules::HARDBLANK) && left == hardblank && right == hardblank {
+            return Some(left);
+        }
+        if left == hardblank || right == hardblank {
+            return None;
+        }
+        if rules.contains(SmushRules::EQUAL_CHARACTER) && left == right {
+            return Some(left);
+        }
+        if rules.contains(SmushRules::UNDERSCORE) {
+            const REPLACING: &str = "|/\\[]{}()<>";
+            if left == '_' && REPLACING.contains(right) {
+                return Some(right);
+            }
+            if right == '_' && REPLACING.contains(left) {
+                return Some(left);
+            }
+        }
+        if rules.contains(SmushRules::HIERARCHY) {
+            const CLASSES: [&str; 6] = ["|", "/\\", "[]", "{}", "()", "<>"];
+            let class = |ch: char| CLASSES.iter().position(|class| class.contains(ch));
+            if let (Some(l), Some(r)) = (class(left), class(right)) {
+                if l != r {
+                    return Some(if l > r { left } else { right });
+                }
+            }
+        }
+        if rules.contains(SmushRules::OPPOSITE_PAIR)
+            && matches!((left, right), ('[', ']') | (']', '[') | ('{', '}') | ('}', '{') | ('(', ')') | (')', '('))
+        {
+            return Some('|');
+        }
+        if rules.contains(SmushRules::BIG_X) {
+            match (left, right) {
+                ('/', '\\') => return Some('|'),
+                ('\\', '/') => return Some('Y'),
+                ('>', '<') => return Some('X'),
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+/// Returns the layout given by the old and full layout fields of a font header.
+fn header_layout(old_layout: i32, full_layout: Option<i32>) -> Layout {
+    match full_layout {
+        Some(full) if full & 128 != 0 => Layout::Smushing(SmushRules::from_bits(full as u8)),
+        Some(full) if full & 64 != 0 => Layout::Fitting,
+        Some(_) => Layout::FullWidth,
+        None if old_layout < 0 => Layout::FullWidth,
+        None if old_layout == 0 => Layout::Fitting,
+        None => Layout::Smushing(SmushRules::from_bits(old_layout as u8)),
+    }
+}
+
+/// Parses a code tag line, returning `Some(None)` for a negative code.
+fn code_tag(line: &str) -> Option<Option<u32>> {
+    let code = line.split_whitespace().next()?;
+    let (negative, code) = match code.strip_prefix('-') {
+        Some(code) => (true, code),
+        None => (false, code),
+    };
+    let value = if let Some(hex) = code.strip_prefix("0x").or_else(|| code.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16).ok()?
+    } else if code.len() > 1 && code.starts_with('0') {
+        u32::from_str_radix(&code[1..], 8).ok()?
+    } else {
+        code.parse().ok()?
+    };
+    Some((!negative).then_some(value))
+}
+
+/// Returns the characters of a glyph row without its trailing endmarks.
+fn glyph_row(line: &str) -> Vec<char> {
+    let mut row: Vec<char> = line.trim_end().chars().collect();
+    if let Some(&endmark) = row.last() {
+        while row.last() == Some(&endmark) {
+            row.pop();
+        }
+    }
+    row
+}
+
+/// Pads the rows of a glyph with spaces to the width of the widest one.
+fn pad(mut rows: Vec<Vec<char>>) -> Vec<Vec<char>> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    for row in &mut rows {
+        row.resize(width, ' ');
+    }
+    rows
+}
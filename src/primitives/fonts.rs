@@ -0,0 +1,307 @@
+// Created by Sean L. on Jun. 25.
+// Last Updated by Sean L. on Jun. 25.
+//
+// overture.rs
+// src/primitives/fonts.rs
+//
+// Makabaka1880, 2025. All rights reserved.
+
+//! FIGlet Font Registry
+//!
+//! This module keeps track of the FIGlet and TOIlet fonts available to
+//! [`Text::ascii_art_by_name`](crate::primitives::Text::ascii_art_by_name). Fonts are looked up
+//! by case-insensitive name, parsed on first use and cached from then on.
+//!
+//! # Available Fonts
+//!
+//! - `standard`, the default font of FIGlet, is always available.
+//! - The fonts bundled with Overture are embedded into the binary, each behind its own cargo
+//!   feature: `font-3-d`, `font-3x5`, `font-acrobatic`, `font-alphabet`, `font-big`,
+//!   `font-blocks` and `font-larry3d`. The `all-fonts` feature, enabled by default, enables all
+//!   of them. `blocks` is a TOIlet font drawn in full blocks, meant for big numerals.
+//! - Any other `.flf` or `.tlf` font can be registered at runtime, from a file with
+//!   [`register_font_file`] or from its content with [`register_font`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use overture::primitives::fonts;
+//!
+//! assert!(fonts::available().contains(&"standard".to_string()));
+//!
+//! let flf = fonts::font("standard").unwrap();
+//! let banner = flf.convert("Hi").unwrap();
+//!
+//! // The same font, parsed for FIGlet layout
+//! let font = fonts::try_figlet_font("standard").unwrap();
+//! assert_eq!(font.height(), 6);
+//! ```
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use figlet_rs::FIGfont;
+
+use crate::error::{OvertureError, OvertureResult};
+use crate::primitives::figlet::FigletFont;
+
+/// The FIGfonts known by name, parsed lazily.
+///
+/// Every font is parsed once, as a [`FigletFont`], which keeps what FIGlet layout needs. The
+/// [`FIGfont`] of a font is converted from it on first use.
+///
+/// Most code uses the global registry through the functions of this module; a separate
+/// `FontRegistry` is only needed to keep fonts apart, e.g. in tests.
+///
+/// # Examples
+///
+/// ```rust
+/// use overture::primitives::fonts::FontRegistry;
+///
+/// let mut registry = FontRegistry::new();
+/// assert!(registry.get("STANDARD").is_some());
+/// assert!(registry.get("my-font").is_none());
+///
+/// let flf = std::fs::read_to_string("fonts/big.flf").unwrap();
+/// registry.register("my-font", &flf).unwrap();
+/// assert!(registry.get("my-font").is_some());
+/// assert!(registry.register("broken", "not a font").is_err());
+/// ```
+#[derive(Debug, Default)]
+pub struct FontRegistry {
+    /// Fonts embedded into the crate, not parsed yet.
+    embedded: HashMap<String, &'static str>,
+    /// Fonts parsed so far, including every registered font.
+    parsed: HashMap<String, ParsedFont>,
+}
+
+/// A parsed font, and its [`FIGfont`] once converted.
+#[derive(Debug)]
+struct ParsedFont {
+    font: Arc<FigletFont>,
+    figfont: Option<Arc<FIGfont>>,
+}
+
+impl ParsedFont {
+    fn new(font: FigletFont) -> Self {
+        ParsedFont { font: Arc::new(font), figfont: None }
+    }
+}
+
+impl FontRegistry {
+    /// The name of the default FIGlet font.
+    pub const STANDARD: &'static str = "standard";
+
+    /// Creates a registry holding the standard font and the fonts embedded into the crate.
+    pub fn new() -> Self {
+        FontRegistry {
+            embedded: embedded_fonts().into_iter().map(|(name, flf)| (name.to_string(), flf)).collect(),
+            parsed: HashMap::new(),
+        }
+    }
+
+    /// Returns the names of all fonts in the registry, sorted.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.embedded
+            .keys()
+            .chain(self.parsed.keys())
+            .cloned()
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Returns `true` if a font with the given name is in the registry.
+    pub fn contains(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.embedded.contains_key(&name) || self.parsed.contains_key(&name)
+    }
+
+    /// Returns the font with the given name, parsing it on first use.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no such font exists, or if the embedded font fails to parse.
+    pub fn get(&mut self, name: &str) -> Option<Arc<FIGfont>> {
+        self.try_get(name).ok()
+    }
+
+    /// Returns the font with the given name, parsing it on first use.
+    ///
+    /// # Returns
+    ///
+    /// [`OvertureError::UnknownFont`] if no such font exists, or [`OvertureError::InvalidFont`]
+    /// if it fails to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::OvertureError;
+    /// use overture::primitives::fonts::FontRegistry;
+    ///
+    /// let mut registry = FontRegistry::new();
+    /// let error = registry.try_get("larry4d").unwrap_err();
+    /// assert!(matches!(error, OvertureError::UnknownFont { .. }));
+    /// assert!(error.to_string().starts_with("unknown font \"larry4d\""));
+    /// ```
+    pub fn try_get(&mut self, name: &str) -> OvertureResult<Arc<FIGfont>> {
+        let parsed = self.parsed_font(name)?;
+        Ok(parsed.figfont.get_or_insert_with(|| Arc::new(parsed.font.to_figfont())).clone())
+    }
+
+    /// Returns the font with the given name parsed for FIGlet layout, parsing it on first use.
+    ///
+    /// # Returns
+    ///
+    /// [`OvertureError::UnknownFont`] if no such font exists, or [`OvertureError::InvalidFont`]
+    /// if it fails to parse.
+    pub fn try_get_figlet(&mut self, name: &str) -> OvertureResult<Arc<FigletFont>> {
+        Ok(self.parsed_font(name)?.font.clone())
+    }
+
+    /// Returns the entry of the font with the given name, parsing it on first use.
+    fn parsed_font(&mut self, name: &str) -> OvertureResult<&mut ParsedFont> {
+        let key = name.to_lowercase();
+        if !self.parsed.contains_key(&key) {
+            let flf = self
+                .embedded
+                .get(&key)
+                .copied()
+                .ok_or_else(|| OvertureError::UnknownFont { name: name.to_string(), available: self.names() })?;
+            self.parsed.insert(key.clone(), ParsedFont::new(parse(name, flf)?));
+        }
+        Ok(self.parsed.get_mut(&key).expect("font parsed above"))
+    }
+
+    /// Parses `flf`, the content of a FIGlet or TOIlet font file, and registers it as `name`.
+    ///
+    /// A font already registered under that name, including a bundled one, is replaced.
+    ///
+    /// # Returns
+    ///
+    /// [`OvertureError::InvalidFont`] if `flf` is not a valid font.
+    pub fn register(&mut self, name: &str, flf: &str) -> OvertureResult<()> {
+        let font = parse(name, flf)?;
+        let name = name.to_lowercase();
+        self.embedded.remove(&name);
+        self.parsed.insert(name, ParsedFont::new(font));
+        Ok(())
+    }
+
+    /// Loads the FIGlet or TOIlet font file at `path` and registers it under its file name
+    /// without the extension, e.g. `fonts/slant.flf` as `slant`.
+    ///
+    /// # Returns
+    ///
+    /// The name the font was registered as, or the error reading or parsing the file.
+    /// IO errors mention the path of the file.
+    pub fn register_file<P: AsRef<Path>>(&mut self, path: P) -> OvertureResult<String> {
+        let path = path.as_ref();
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("no font name in {}", path.display())))?
+            .to_lowercase();
+        let flf = std::fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+        self.register(&name, &flf)?;
+        Ok(name)
+    }
+}
+
+/// Returns the global registry, creating it on first use.
+fn registry() -> std::sync::MutexGuard<'static, FontRegistry> {
+    static REGISTRY: OnceLock<Mutex<FontRegistry>> = OnceLock::new();
+    REGISTRY
+        .get_or_init(|| Mutex::new(FontRegistry::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Returns the names of all fonts in the global registry, sorted.
+pub fn available() -> Vec<String> {
+    registry().names()
+}
+
+/// Returns the font with the given name from the global registry, parsing it on first use.
+///
+/// Names are case-insensitive. Returns `None` if no such font exists.
+pub fn font(name: &str) -> Option<Arc<FIGfont>> {
+    registry().get(name)
+}
+
+/// Returns the font with the given name from the global registry, or an error naming it.
+///
+/// See [`FontRegistry::try_get`].
+pub fn try_font(name: &str) -> OvertureResult<Arc<FIGfont>> {
+    registry().try_get(name)
+}
+
+/// Returns the font with the given name from the global registry parsed for FIGlet layout,
+/// or an error naming it.
+///
+/// See [`FontRegistry::try_get_figlet`].
+pub fn try_figlet_font(name: &str) -> OvertureResult<Arc<FigletFont>> {
+    registry().try_get_figlet(name)
+}
+
+/// Parses `flf`, the content of a FIGlet or TOIlet font file, and adds it to the global registry
+/// as `name`.
+///
+/// See [`FontRegistry::register`].
+///
+/// # Examples
+///
+/// ```rust
+/// use overture::primitives::fonts;
+///
+/// let flf = std::fs::read_to_string("fonts/big.flf").unwrap();
+/// fonts::register_font("Tall", &flf).unwrap();
+/// assert!(fonts::font("tall").is_some());
+/// assert!(fonts::available().contains(&"tall".to_string()));
+/// ```
+pub fn register_font(name: &str, flf: &str) -> OvertureResult<()> {
+    registry().register(name, flf)
+}
+
+/// Loads the FIGlet or TOIlet font file at `path` and adds it to the global registry under its
+/// file name.
+///
+/// See [`FontRegistry::register_file`].
+pub fn register_font_file<P: AsRef<Path>>(path: P) -> OvertureResult<String> {
+    registry().register_file(path)
+}
+
+/// Parses the content of the FIGlet or TOIlet font file of the font `name`.
+fn parse(name: &str, flf: &str) -> OvertureResult<FigletFont> {
+    FigletFont::parse(flf).map_err(|reason| OvertureError::InvalidFont { name: name.to_string(), reason })
+}
+
+/// Returns the names and contents of the fonts embedded into the crate: the standard font,
+/// and the fonts enabled by features.
+///
+/// `figlet_rs` embeds the standard font too, but only exposes it parsed by
+/// [`FIGfont::standard`], which replaces hardblanks with spaces. Layout needs the hardblanks,
+/// so the crate embeds its own copy of `standard.flf`.
+#[allow(unused_mut, clippy::vec_init_then_push)]
+fn embedded_fonts() -> Vec<(&'static str, &'static str)> {
+    let mut fonts = vec![(FontRegistry::STANDARD, include_str!("../../fonts/standard.flf"))];
+    #[cfg(feature = "font-3-d")]
+    fonts.push(("3-d", include_str!("../../fonts/3-d.flf")));
+    #[cfg(feature = "font-3x5")]
+    fonts.push(("3x5", include_str!("../../fonts/3x5.flf")));
+    #[cfg(feature = "font-acrobatic")]
+    fonts.push(("acrobatic", include_str!("../../fonts/acrobatic.flf")));
+    #[cfg(feature = "font-alphabet")]
+    fonts.push(("alphabet", include_str!("../../fonts/alphabet.flf")));
+    #[cfg(feature = "font-big")]
+    fonts.push(("big", include_str!("../../fonts/big.flf")));
+    #[cfg(feature = "font-blocks")]
+    fonts.push(("blocks", include_str!("../../fonts/blocks.tlf")));
+    #[cfg(feature = "font-larry3d")]
+    fonts.push(("larry3d", include_str!("../../fonts/larry3d.flf")));
+    fonts
+}
@@ -1,5 +1,5 @@
 // Created by Sean L. on Jun. 23.
-// Last Updated by Sean L. on Jun. 23.
+// Last Updated by Sean L. on Jun. 25.
 // 
 // Overture.rs
 // src/primitives/mod.rs
@@ -14,9 +14,13 @@
 //! # Submodules
 //! - [`shape`]: Contains definitions and utilities for geometric shapes.
 //! - [`text`]: Provides structures and functions for handling text primitives.
+//! - [`paragraph`]: Text wrapped and aligned to a fixed width.
+//! - [`fonts`]: The registry of FIGlet fonts used for ASCII art.
+//! - [`figlet`]: FIGlet layout modes, smushing and fitting banners to a width.
 //!
 //! # Re-exports
 //! - [`Text`]: The main text primitive type, re-exported for convenience.
+//! - [`Paragraph`]: The wrapped text primitive, re-exported for convenience.
 //!
 //! # Examples
 //! ```rust
@@ -26,5 +30,9 @@
 
 pub mod shape;
 pub mod text;
+pub mod paragraph;
+pub mod fonts;
+pub mod figlet;
 
-pub use crate::primitives::text::Text;
\ No newline at end of file
+pub use crate::primitives::text::Text;
+pub use crate::primitives::paragraph::Paragraph;
\ No newline at end of file
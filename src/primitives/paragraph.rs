@@ -0,0 +1,425 @@
+// Created by Sean L. on Jun. 25.
+// Last Updated by Sean L. on Jun. 25.
+//
+// overture.rs
+// src/primitives/paragraph.rs
+//
+// Makabaka1880, 2025. All rights reserved.
+
+//! Paragraph Primitive for Flowing Prose
+//!
+//! This module provides [`Paragraph`], a block of text wrapped to a fixed width. Where
+//! [`Text`](crate::primitives::Text) draws its content exactly as given, a paragraph breaks it
+//! into lines that fit the width, then aligns every line within it.
+//!
+//! - [`Wrap::Word`] breaks lines between words, or after the hyphen of a hyphenated word.
+//!   Runs of whitespace collapse into single spaces, and words wider than the paragraph are
+//!   broken wherever they have to be.
+//! - [`Wrap::Char`] breaks lines at the last grapheme that fits, keeping whitespace as it is.
+//!
+//! Lines are measured in display width, so wide characters count as two columns and are
+//! never split across lines. Every `\n` in the content starts a new paragraph. Tabs and other
+//! control characters become single spaces in either mode, as they would move the cursor if
+//! printed.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use overture::prelude::*;
+//! use overture::primitives::paragraph::{Alignment, Paragraph};
+//!
+//! let paragraph = Paragraph::new("The quick brown fox jumps over the lazy dog", DiscreteCoord::ORIGIN, 16)
+//!     .with_alignment(Alignment::Justify);
+//! assert_eq!(paragraph.lines(), ["The  quick brown", "fox  jumps  over", "the lazy dog"]);
+//!
+//! // Pixels can be styled and placed like those of any other primitive
+//! let pixels = paragraph.rasterize().style(style![ANSISequence::Italic]);
+//! ```
+
+use crate::interfaces::{
+    geometry::DiscreteCoord,
+    grapheme::{graphemes, Grapheme},
+    pixels::Pixel,
+    rendering::Renderable,
+};
+use crate::primitives::text::push_grapheme;
+
+/// Where a [`Paragraph`] may break its lines.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Wrap {
+    /// Breaks between words and after hyphens. The default.
+    #[default]
+    Word,
+    /// Breaks after the last grapheme that fits, even in the middle of a word.
+    Char,
+}
+
+/// How the lines of a [`Paragraph`] are positioned within its width.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Alignment {
+    /// Lines start at the left edge. The default.
+    #[default]
+    Left,
+    /// Lines end at the right edge.
+    Right,
+    /// Lines are centered, leaning left when the leftover space is odd.
+    Center,
+    /// Spaces between words are widened so that lines span the full width. The last line of
+    /// every paragraph, and lines without spaces, are left-aligned. Only applies to
+    /// [`Wrap::Word`]; character-wrapped text is left-aligned instead.
+    Justify,
+}
+
+/// A block of text wrapped to a fixed width.
+///
+/// Configured with `with_*` methods. Implements [`Renderable`], producing one protected pixel
+/// per grapheme and per space between words. The space used for alignment has no pixels.
+///
+/// # Examples
+///
+/// ```rust
+/// use overture::prelude::*;
+/// use overture::primitives::paragraph::{Alignment, Paragraph, Wrap};
+///
+/// let paragraph = Paragraph::new("state-of-the-art terminal rendering", DiscreteCoord::ORIGIN, 12)
+///     .with_alignment(Alignment::Right)
+///     .with_max_lines(2);
+/// assert_eq!(paragraph.lines(), ["   state-of-", "    the-art…"]);
+/// assert_eq!(paragraph.dim(), DiscreteCoord::new(12, 2));
+///
+/// let chars = Paragraph::new("abcdefgh", DiscreteCoord::ORIGIN, 3).with_wrap(Wrap::Char);
+/// assert_eq!(chars.lines(), ["abc", "def", "gh"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Paragraph {
+    /// The text of the paragraph.
+    pub content: String,
+
+    /// The position of the paragraph's top-left corner.
+    pub pos: DiscreteCoord,
+
+    /// The width the text is wrapped to, in columns.
+    pub width: u32,
+
+    /// Where lines may be broken.
+    pub wrap: Wrap,
+
+    /// How lines are positioned within the width.
+    pub alignment: Alignment,
+
+    /// The maximum number of lines shown, if any. Text beyond it is cut off, and the
+    /// [`ellipsis`](Paragraph::ellipsis) ends the last line shown.
+    pub max_lines: Option<u32>,
+
+    /// The text marking that the paragraph was cut off. Defaults to `…`.
+    pub ellipsis: String,
+}
+
+/// A run of graphemes that is never broken, unless it does not fit on a line by itself.
+#[derive(Clone, Debug)]
+struct Piece {
+    graphemes: Vec<Grapheme>,
+    width: u32,
+    /// Whether the piece is separated from the one before it by a space.
+    spaced: bool,
+}
+
+impl Piece {
+    fn new(graphemes: Vec<Grapheme>, spaced: bool) -> Self {
+        let width = graphemes.iter().map(cell_width).sum();
+        Piece { graphemes, width, spaced }
+    }
+}
+
+/// A line of a wrapped paragraph, before alignment.
+#[derive(Clone, Debug, Default)]
+struct Line {
+    pieces: Vec<Piece>,
+    /// Whether the line ends a paragraph, and therefore is not justified.
+    last: bool,
+}
+
+impl Line {
+    fn width(&self) -> u32 {
+        self.pieces
+            .iter()
+            .enumerate()
+            .map(|(i, piece)| piece.width + u32::from(i > 0 && piece.spaced))
+            .sum()
+    }
+
+    fn gaps(&self) -> u32 {
+        self.pieces.iter().skip(1).filter(|piece| piece.spaced).count() as u32
+    }
+}
+
+impl Paragraph {
+    /// Creates a left-aligned, word-wrapped paragraph without a line limit.
+    ///
+    /// # Parameters
+    ///
+    /// - `content`: Text content (any type convertible into `String`).
+    /// - `pos`: Position of the paragraph's top-left corner.
+    /// - `width`: The width to wrap the text to. Clamped to at least 1.
+    ///
+    /// # Returns
+    ///
+    /// A `Paragraph` instance ready to be rendered.
+    pub fn new<S: Into<String>>(content: S, pos: DiscreteCoord, width: u32) -> Self {
+        Paragraph {
+            content: content.into(),
+            pos,
+            width: width.max(1),
+            wrap: Wrap::default(),
+            alignment: Alignment::default(),
+            max_lines: None,
+            ellipsis: String::from("…"),
+        }
+    }
+
+    /// Returns this paragraph with the given [`Wrap`] mode.
+    pub fn with_wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Returns this paragraph with the given [`Alignment`].
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Returns this paragraph limited to `max_lines` lines.
+    pub fn with_max_lines(mut self, max_lines: u32) -> Self {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
+    /// Returns this paragraph with `ellipsis` marking text that was cut off.
+    ///
+    /// An empty ellipsis cuts the text off silently.
+    pub fn with_ellipsis<S: Into<String>>(mut self, ellipsis: S) -> Self {
+        self.ellipsis = ellipsis.into();
+        self
+    }
+
+    /// Returns the wrapped and aligned lines, without trailing blanks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::prelude::*;
+    /// use overture::primitives::paragraph::{Alignment, Paragraph};
+    ///
+    /// let paragraph = Paragraph::new("Hello there\nworld", DiscreteCoord::ORIGIN, 9)
+    ///     .with_alignment(Alignment::Center);
+    /// assert_eq!(paragraph.lines(), ["  Hello", "  there", "  world"]);
+    /// ```
+    pub fn lines(&self) -> Vec<String> {
+        self.layout()
+            .into_iter()
+            .map(|cells| {
+                let mut line = String::new();
+                let mut column = 0;
+                for (x, grapheme) in cells {
+                    line.extend(std::iter::repeat_n(' ', (x - column) as usize));
+                    grapheme.push_to(&mut line);
+                    column = x + cell_width(&grapheme);
+                }
+                line
+            })
+            .collect()
+    }
+
+    /// Wraps, truncates and aligns the paragraph, returning the graphemes of each line
+    /// with their columns.
+    fn layout(&self) -> Vec<Vec<(u32, Grapheme)>> {
+        let mut lines = vec![];
+        for paragraph in self.content.split('\n') {
+            let paragraph: String = paragraph
+                .strip_suffix('\r')
+                .unwrap_or(paragraph)
+                .chars()
+                .map(|ch| if ch.is_control() { ' ' } else { ch })
+                .collect();
+            let start = lines.len();
+            match self.wrap {
+                Wrap::Word => self.wrap_words(&paragraph, &mut lines),
+                Wrap::Char => self.wrap_chars(&paragraph, &mut lines),
+            }
+            if lines.len() == start {
+                lines.push(Line::default());
+            }
+            if let Some(line) = lines.last_mut() {
+                line.last = true;
+            }
+        }
+
+        if let Some(max) = self.max_lines.map(|max| max as usize) {
+            if lines.len() > max {
+                lines.truncate(max);
+                if let Some(line) = lines.last_mut() {
+                    self.ellipsize(line);
+                }
+            }
+        }
+
+        lines.iter().map(|line| self.align(line)).collect()
+    }
+
+    /// Breaks a paragraph between words, appending its lines to `lines`.
+    fn wrap_words(&self, paragraph: &str, lines: &mut Vec<Line>) {
+        let mut line = Line::default();
+        for word in paragraph.split_whitespace() {
+            for (i, segment) in split_hyphens(graphemes(word)).into_iter().enumerate() {
+                let piece = Piece::new(segment, i == 0);
+                let needed = piece.width + u32::from(piece.spaced && !line.pieces.is_empty());
+                if line.width() + needed <= self.width {
+                    line.pieces.push(piece);
+                    continue;
+                }
+                if !line.pieces.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                // The piece starts a line; break it up if it is still too wide
+                let mut chunks = self.break_graphemes(piece.graphemes);
+                let rest = chunks.pop().unwrap_or_default();
+                lines.extend(chunks.into_iter().map(|chunk| Line { pieces: vec![Piece::new(chunk, false)], last: false }));
+                line.pieces.push(Piece::new(rest, false));
+            }
+        }
+        if !line.pieces.is_empty() {
+            lines.push(line);
+        }
+    }
+
+    /// Breaks a paragraph at the last grapheme that fits, appending its lines to `lines`.
+    fn wrap_chars(&self, paragraph: &str, lines: &mut Vec<Line>) {
+        lines.extend(
+            self.break_graphemes(graphemes(paragraph))
+                .into_iter()
+                .map(|chunk| Line { pieces: vec![Piece::new(chunk, false)], last: false }),
+        );
+    }
+
+    /// Splits graphemes into chunks no wider than the paragraph. A grapheme wider than the
+    /// paragraph gets a chunk of its own.
+    fn break_graphemes(&self, graphemes: Vec<Grapheme>) -> Vec<Vec<Grapheme>> {
+        let mut chunks = vec![];
+        let mut chunk = vec![];
+        let mut width = 0;
+        for grapheme in graphemes {
+            let w = cell_width(&grapheme);
+            if width + w > self.width && !chunk.is_empty() {
+                chunks.push(std::mem::take(&mut chunk));
+                width = 0;
+            }
+            chunk.push(grapheme);
+            width += w;
+        }
+        if !chunk.is_empty() {
+            chunks.push(chunk);
+        }
+        chunks
+    }
+
+    /// Shortens `line` until the ellipsis fits behind it, then appends the ellipsis.
+    fn ellipsize(&self, line: &mut Line) {
+        let ellipsis = graphemes(&self.ellipsis);
+        let ellipsis_width: u32 = ellipsis.iter().map(cell_width).sum();
+        while line.width() + ellipsis_width > self.width {
+            let Some(piece) = line.pieces.last_mut() else { break };
+            match piece.graphemes.pop() {
+                Some(grapheme) => piece.width -= cell_width(&grapheme),
+                None => {
+                    line.pieces.pop();
+                }
+            }
+        }
+        line.pieces.retain(|piece| !piece.graphemes.is_empty());
+        line.pieces.push(Piece::new(ellipsis, false));
+        line.last = true;
+    }
+
+    /// Positions the graphemes of `line` according to the alignment.
+    fn align(&self, line: &Line) -> Vec<(u32, Grapheme)> {
+        let free = self.width.saturating_sub(line.width());
+        let gaps = line.gaps();
+        let justify = self.alignment == Alignment::Justify && self.wrap == Wrap::Word && !line.last && gaps > 0;
+
+        let mut column = match self.alignment {
+            Alignment::Left | Alignment::Justify => 0,
+            Alignment::Right => free,
+            Alignment::Center => free / 2,
+        };
+        let mut gap = 0;
+        let mut cells = vec![];
+        for (i, piece) in line.pieces.iter().enumerate() {
+            if i > 0 && piece.spaced {
+                let mut spaces = 1;
+                if justify {
+                    // Spread the free columns evenly, giving the leftmost gaps one more
+                    spaces += free / gaps + u32::from(gap < free % gaps);
+                }
+                cells.extend((column..column + spaces).map(|x| (x, Grapheme::from_char(' '))));
+                column += spaces;
+                gap += 1;
+            }
+            for grapheme in &piece.graphemes {
+                cells.push((column, grapheme.clone()));
+                column += cell_width(grapheme);
+            }
+        }
+        cells
+    }
+}
+
+impl Renderable for Paragraph {
+    /// Converts the paragraph into pixels, one row per line, starting at `pos`.
+    ///
+    /// Wide graphemes are followed by a continuation pixel, like in [`Text`](crate::primitives::Text).
+    fn pixels(&self) -> Vec<Pixel> {
+        let mut pixels = vec![];
+        for (row, cells) in self.layout().into_iter().enumerate() {
+            for (column, grapheme) in cells {
+                push_grapheme(&mut pixels, grapheme, self.pos + DiscreteCoord::new(column, row as u32));
+            }
+        }
+        pixels
+    }
+
+    /// Returns the dimensions of the paragraph as (width, number of lines).
+    fn dim(&self) -> DiscreteCoord {
+        DiscreteCoord::new(self.width, self.layout().len() as u32)
+    }
+}
+
+/// Returns the number of cells a grapheme is drawn into.
+fn cell_width(grapheme: &Grapheme) -> u32 {
+    grapheme.width().max(1)
+}
+
+/// Splits a word after each single hyphen between two other characters, e.g. `well-known`
+/// into `well-` and `known`. Leading, trailing and doubled hyphens are not break points.
+fn split_hyphens(word: Vec<Grapheme>) -> Vec<Vec<Grapheme>> {
+    let breaks: Vec<bool> = (0..word.len())
+        .map(|i| {
+            word[i] == '-'
+                && i > 0 && word[i - 1] != '-'
+                && word.get(i + 1).is_some_and(|next| *next != '-')
+        })
+        .collect();
+
+    let mut segments = vec![];
+    let mut segment = vec![];
+    for (grapheme, breaks) in word.into_iter().zip(breaks) {
+        segment.push(grapheme);
+        if breaks {
+            segments.push(std::mem::take(&mut segment));
+        }
+    }
+    if !segment.is_empty() {
+        segments.push(segment);
+    }
+    segments
+}
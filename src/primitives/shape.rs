@@ -17,6 +17,8 @@
 //!
 //! - [`Rectangle`]: A rectangular box with sharp corners, rendered using standard box-drawing characters.
 //! - [`SoftBox`]: A rectangular box with rounded (soft) corners, rendered using soft box-drawing characters.
+//! - [`Fill`]: A solid rectangular area, useful as a backdrop or, with
+//!   [`Transparency::KeepChar`](crate::interfaces::rendering::Transparency::KeepChar), as a tint.
 //!
 //! All shapes are internally normalized so that their `pos` field always represents
 //! the top-left corner and `corner` the bottom-right corner, regardless of the order
@@ -141,12 +143,13 @@ impl Renderable for Rectangle {
     ///
     /// A `Vec<Pixel>` containing all the pixels needed to draw the rectangle's border.
     fn pixels(&self) -> Vec<Pixel> {
-        let mut pixels = vec![];
         // Corners
-        pixels.push(Pixel::new_with_char(box_drawing::LU_CORNER, self.pos, false));
-        pixels.push(Pixel::new_with_char(box_drawing::RD_CORNER, self.corner, false));
-        pixels.push(Pixel::new_with_char(box_drawing::LD_CORNER, DiscreteCoord::new(self.pos.x, self.corner.y), false));
-        pixels.push(Pixel::new_with_char(box_drawing::RU_CORNER, DiscreteCoord::new(self.corner.x, self.pos.y), false));
+        let mut pixels = vec![
+            Pixel::new_with_char(box_drawing::LU_CORNER, self.pos, false),
+            Pixel::new_with_char(box_drawing::RD_CORNER, self.corner, false),
+            Pixel::new_with_char(box_drawing::LD_CORNER, DiscreteCoord::new(self.pos.x, self.corner.y), false),
+            Pixel::new_with_char(box_drawing::RU_CORNER, DiscreteCoord::new(self.corner.x, self.pos.y), false),
+        ];
         // Top and bottom edges
         for x in (self.pos.x + 1)..self.corner.x {
             pixels.push(Pixel::new_with_char(box_drawing::H_LINE, DiscreteCoord::new(x, self.pos.y), false));
@@ -254,12 +257,13 @@ impl Renderable for SoftBox {
     ///
     /// A `Vec<Pixel>` containing all the pixels needed to draw the soft box's border.
     fn pixels(&self) -> Vec<Pixel> {
-        let mut pixels = vec![];
         // Corners with soft rounded characters
-        pixels.push(Pixel::new_with_char(box_drawing::LU_CORNER_SOFT, self.pos, false));
-        pixels.push(Pixel::new_with_char(box_drawing::RD_CORNER_SOFT, self.corner, false));
-        pixels.push(Pixel::new_with_char(box_drawing::LD_CORNER_SOFT, DiscreteCoord::new(self.pos.x, self.corner.y), false));
-        pixels.push(Pixel::new_with_char(box_drawing::RU_CORNER_SOFT, DiscreteCoord::new(self.corner.x, self.pos.y), false));
+        let mut pixels = vec![
+            Pixel::new_with_char(box_drawing::LU_CORNER_SOFT, self.pos, false),
+            Pixel::new_with_char(box_drawing::RD_CORNER_SOFT, self.corner, false),
+            Pixel::new_with_char(box_drawing::LD_CORNER_SOFT, DiscreteCoord::new(self.pos.x, self.corner.y), false),
+            Pixel::new_with_char(box_drawing::RU_CORNER_SOFT, DiscreteCoord::new(self.corner.x, self.pos.y), false),
+        ];
         // Top and bottom edges
         for x in (self.pos.x + 1)..self.corner.x {
             pixels.push(Pixel::new_with_char(box_drawing::H_LINE, DiscreteCoord::new(x, self.pos.y), false));
@@ -278,3 +282,68 @@ impl Renderable for SoftBox {
         self.corner - self.pos
     }
 }
+
+/// A solid rectangular area filled with a single character, defined by two points in 2D space.
+///
+/// Fills are spaces by default. Their pixels are protected so that blank fills survive
+/// [`Renderable::prune`]. Combined with [`Renderable::with_transparency`], a fill can tint
+/// a region without erasing what is below it.
+///
+/// # Examples
+///
+/// ```rust
+/// use overture::primitives::shape::Fill;
+/// use overture::interfaces::{geometry::DiscreteCoord, rendering::Renderable};
+///
+/// let fill = Fill::new(DiscreteCoord::new(4, 3), DiscreteCoord::new(1, 1)).with_char('#');
+/// assert_eq!(fill.pos(), DiscreteCoord::new(1, 1));
+/// assert_eq!(fill.pixels().len(), 4 * 3);
+/// ```
+pub struct Fill {
+    pos: DiscreteCoord,
+    corner: DiscreteCoord,
+    ch: char,
+}
+
+impl Fill {
+    /// Returns the top-left corner of the fill.
+    pub fn pos(&self) -> DiscreteCoord { self.pos }
+
+    /// Returns the bottom-right corner of the fill.
+    pub fn corner(&self) -> DiscreteCoord { self.corner }
+
+    /// Creates a new `Fill` of spaces covering the rectangle spanned by two arbitrary points (inclusive).
+    ///
+    /// # Parameters
+    ///
+    /// - `p1`: One corner of the area.
+    /// - `p2`: The opposite corner of the area.
+    pub fn new(p1: DiscreteCoord, p2: DiscreteCoord) -> Self {
+        Fill {
+            pos: DiscreteCoord::new(p1.x.min(p2.x), p1.y.min(p2.y)),
+            corner: DiscreteCoord::new(p1.x.max(p2.x), p1.y.max(p2.y)),
+            ch: ' ',
+        }
+    }
+
+    /// Returns this fill using `ch` instead of spaces.
+    pub fn with_char(mut self, ch: char) -> Self {
+        self.ch = ch;
+        self
+    }
+}
+
+impl Renderable for Fill {
+    /// Returns one protected pixel per cell of the area, in row-major order.
+    fn pixels(&self) -> Vec<Pixel> {
+        (self.pos.y..=self.corner.y)
+            .flat_map(|y| (self.pos.x..=self.corner.x).map(move |x| DiscreteCoord::new(x, y)))
+            .map(|pos| Pixel::new_with_char(self.ch, pos, true))
+            .collect()
+    }
+
+    /// Returns the dimensions of the fill as a `DiscreteCoord` representing width and height.
+    fn dim(&self) -> DiscreteCoord {
+        self.corner - self.pos
+    }
+}
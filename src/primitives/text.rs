@@ -1,5 +1,5 @@
 // Created by Sean L. on Jun. 22.
-// Last Updated by Sean L. on Jun. 24.
+// Last Updated by Sean L. on Jun. 25.
 // 
 // overture.rs
 // src/primitives/text.rs
@@ -13,14 +13,26 @@
 //! the [`Renderable`] trait to convert the text into a sequence of pixels
 //! for rendering.
 //!
-//! Additionally, `Text` supports ASCII art rendering using `figlet_rs` fonts,
-//! allowing text to be transformed into large decorative ASCII banners.
+//! Text may span several lines: every `\n` starts a new line at the original x position,
+//! and tabs are expanded to the next tab stop.
+//!
+//! Additionally, `Text` supports ASCII art rendering using FIGlet fonts,
+//! allowing text to be transformed into large decorative ASCII banners. Fonts from the
+//! [font registry](crate::primitives::fonts) are laid out by [`FigletFont`](figlet::FigletFont):
+//! [`Text::ascii_art_by_name`] at full width, and [`Text::ascii_art_with`] with the
+//! [FIGlet layout](crate::primitives::figlet) of your choice, the font's own by default. A
+//! [`figlet_rs::FIGfont`] passed to [`Text::ascii_art`] is rendered by `figlet_rs`, at full
+//! width as well.
 
 use crate::interfaces::{
     geometry::DiscreteCoord,
     pixels::Pixel,
-    rendering::Renderable,
+    grapheme::{graphemes, Grapheme},
+    rendering::{Renderable, RenderChar},
+    styling::RenderStyle,
 };
+use crate::error::{OvertureError, OvertureResult};
+use crate::primitives::{figlet::{self, FigletOptions}, fonts};
 use std::ops::Deref;
 
 /// A textual content positioned in 2D discrete terminal space.
@@ -28,6 +40,14 @@ use std::ops::Deref;
 /// Holds the string content and its starting position.
 /// Implements [`Renderable`] to convert into pixels for rendering.
 ///
+/// A `\n` starts a new line one row down, at the x position of the text (a `\r\n` pair is
+/// treated the same way). A `\t` is expanded with blanks up to the next tab stop, every
+/// [`tab_width`](Text::tab_width) columns from the start of the line.
+///
+/// Characters are laid out by grapheme and display width: wide characters take two cells (the
+/// second one holding a [continuation](RenderChar::continuation)), and combining marks stay in
+/// the cell of the character before them.
+///
 /// # Examples
 ///
 /// ```rust
@@ -37,13 +57,20 @@ use std::ops::Deref;
 /// let text = Text::new("Hello", DiscreteCoord::new(5, 10));
 /// let pixels = text.rasterize();
 /// assert_eq!(pixels.len(), 5);  // One pixel per character
+///
+/// let block = Text::new("name\tvalue\nx\t1", DiscreteCoord::new(5, 10)).with_tab_width(4);
+/// assert_eq!(block.dim(), DiscreteCoord::new(13, 2));
 /// ```
+#[derive(Clone, Debug)]
 pub struct Text {
     /// The string content of this text primitive.
     pub content: String,
 
     /// The position of the text's starting point (top-left corner).
     pub pos: DiscreteCoord,
+
+    /// The distance between tab stops, in columns. Defaults to [`Text::DEFAULT_TAB_WIDTH`].
+    pub tab_width: u32,
 }
 
 impl Text {
@@ -61,12 +88,66 @@ impl Text {
         Text {
             content: content.into(),
             pos,
+            tab_width: Self::DEFAULT_TAB_WIDTH,
+        }
+    }
+
+    /// The default distance between tab stops, matching most terminals.
+    pub const DEFAULT_TAB_WIDTH: u32 = 8;
+
+    /// Returns this text with tab stops every `tab_width` columns.
+    ///
+    /// A width of 0 removes tabs altogether.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::prelude::*;
+    ///
+    /// let text = primitives::Text::new("a\tb", DiscreteCoord::ORIGIN).with_tab_width(4);
+    /// assert_eq!(text.pixels()[4].content.ch, 'b');
+    /// ```
+    pub fn with_tab_width(mut self, tab_width: u32) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    /// Returns the lines of the text, without their line breaks.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.content
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+    }
+
+    /// Lays out a single line, returning each grapheme with its column, and the width of the line.
+    ///
+    /// Tabs are expanded to blanks.
+    fn layout_line(&self, line: &str) -> (Vec<(u32, Grapheme)>, u32) {
+        let mut cells = vec![];
+        let mut column: u32 = 0;
+        for grapheme in graphemes(line) {
+            if grapheme == '\t' {
+                if let Some(stops) = column.checked_div(self.tab_width) {
+                    let stop = (stops + 1) * self.tab_width;
+                    cells.extend((column..stop).map(|x| (x, RenderChar::BLANK_RENDER_CHAR.ch)));
+                    column = stop;
+                }
+                continue;
+            }
+            let width = grapheme.width().max(1);
+            cells.push((column, grapheme));
+            column += width;
         }
+        (cells, column)
     }
 
     /// Converts this `Text` into an ASCII art banner using an optional FIGfont.
     ///
-    /// If no font is provided, the standard FIGfont is used by default.
+    /// If no font is provided, the standard font is used by default, as with
+    /// [`ascii_art_by_name`](Text::ascii_art_by_name). Every line of the text becomes a banner
+    /// of its own, stacked below the previous one, with glyphs at their full width. Characters
+    /// the font has no glyph for are left out; use
+    /// [`try_ascii_art`](Text::try_ascii_art) to be told about them.
     ///
     /// Returns a vector of `Text` lines representing the ASCII art.
     ///
@@ -80,32 +161,51 @@ impl Text {
     /// let art_lines = text.ascii_art(None); // Uses default standard font
     /// ```
     pub fn ascii_art(&self, font: Option<&figlet_rs::FIGfont>) -> Vec<Text> {
-        let font = match font {
-            Some(f) => f,
-            None => &figlet_rs::FIGfont::standard().unwrap(),
-        };
-        let figure = font.convert(&self.content).unwrap();
-
-        figure
-            .to_string()
-            .lines()
-            .enumerate()
-            .map(|(i, line)| {
-                Text::new(line.to_string(), DiscreteCoord::new(
-                    self.pos.x,
-                    self.pos.y + i as u32,
-                ))
-            })
-            .collect()
+        match font {
+            Some(font) => self.convert(font, "custom", false),
+            None => self.full_width_banner(fonts::FontRegistry::STANDARD, false),
+        }
+        .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Converts this `Text` into an ASCII art banner like [`ascii_art`](Text::ascii_art), but
+    /// fails on characters the font has no glyph for.
+    ///
+    /// In errors, a font passed in is called `custom`, and the default one `standard`.
+    ///
+    /// # Returns
+    ///
+    /// The lines of the banner, or [`OvertureError::UnsupportedCharacter`] naming the first
+    /// character without a glyph. An empty text gives no lines.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::prelude::*;
+    /// use overture::OvertureError;
+    ///
+    /// let error = primitives::Text::new("5 €", DiscreteCoord::ORIGIN).try_ascii_art(None).unwrap_err();
+    /// assert!(matches!(error, OvertureError::UnsupportedCharacter { ch: '€', .. }));
+    /// ```
+    pub fn try_ascii_art(&self, font: Option<&figlet_rs::FIGfont>) -> OvertureResult<Vec<Text>> {
+        match font {
+            Some(font) => self.convert(font, "custom", true),
+            None => self.full_width_banner(fonts::FontRegistry::STANDARD, true),
+        }
     }
 
     /// Converts this `Text` into ASCII art using a font loaded by name.
     ///
-    /// Looks for the font file `fonts/{font_name}.flf` relative to the project.
+    /// The font is looked up in the [font registry](crate::primitives::fonts), which holds the
+    /// standard font, the bundled fonts enabled by cargo features and any registered font.
+    /// Glyphs are placed at their full width, whatever layout the font file asks for; use
+    /// [`ascii_art_with`](Text::ascii_art_with) for that layout. Characters the font has no
+    /// glyph for are left out.
     ///
     /// # Panics
     ///
-    /// Panics if the specified font file cannot be loaded.
+    /// Panics if no font with the given name is available. Use
+    /// [`try_ascii_art_by_name`](Text::try_ascii_art_by_name) to handle this instead.
     ///
     /// # Examples
     ///
@@ -117,8 +217,165 @@ impl Text {
     /// let art = text.ascii_art_by_name("larry3d");
     /// ```
     pub fn ascii_art_by_name(&self, font_name: &str) -> Vec<Text> {
-        let font = get_font_by_name(font_name);
-        self.ascii_art(Some(&font))
+        self.full_width_banner(font_name, false)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Converts this `Text` into ASCII art using a font loaded by name, failing on unknown
+    /// fonts and on characters the font has no glyph for.
+    ///
+    /// # Returns
+    ///
+    /// The lines of the banner, [`OvertureError::UnknownFont`] or
+    /// [`OvertureError::InvalidFont`] if the font cannot be loaded, or
+    /// [`OvertureError::UnsupportedCharacter`] naming the font and the first character without
+    /// a glyph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::prelude::*;
+    ///
+    /// let text = primitives::Text::new("Hello", DiscreteCoord::ORIGIN);
+    /// assert!(text.try_ascii_art_by_name("larry3d").is_ok());
+    ///
+    /// let error = text.try_ascii_art_by_name("Larry4D").unwrap_err();
+    /// assert!(error.to_string().contains("Larry4D"));
+    /// ```
+    pub fn try_ascii_art_by_name(&self, font_name: &str) -> OvertureResult<Vec<Text>> {
+        self.full_width_banner(font_name, true)
+    }
+
+    /// Renders every line of the text as a banner in the font `font_name`, at full width.
+    fn full_width_banner(&self, font_name: &str, strict: bool) -> OvertureResult<Vec<Text>> {
+        self.layout_banner(font_name, &FigletOptions::new().with_layout(figlet::Layout::FullWidth), strict)
+    }
+
+    /// Renders every line of the text as a banner in `font`, called `font_name` in errors, using
+    /// `figlet_rs`.
+    ///
+    /// Characters without a glyph are an error if `strict`, and left out otherwise.
+    fn convert(&self, font: &figlet_rs::FIGfont, font_name: &str, strict: bool) -> OvertureResult<Vec<Text>> {
+        if self.content.is_empty() {
+            return Ok(vec![]);
+        }
+        let height = font.header_line.height.max(0) as usize;
+        let mut rows: Vec<String> = vec![];
+        for line in self.lines() {
+            if strict {
+                if let Some(ch) = line.chars().find(|ch| !font.fonts.contains_key(&(*ch as u32))) {
+                    return Err(OvertureError::UnsupportedCharacter { font: font_name.to_string(), ch });
+                }
+            }
+            match font.convert(line) {
+                Some(figure) => rows.extend(figure.to_string().lines().map(str::to_string)),
+                None => rows.extend(std::iter::repeat_n(String::new(), height)),
+            }
+        }
+
+        Ok(rows
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                Text::new(line, DiscreteCoord::new(
+                    self.pos.x,
+                    self.pos.y + i as u32,
+                ))
+            })
+            .collect())
+    }
+
+    /// Converts this `Text` into ASCII art using a font loaded by name, laid out according to
+    /// `options`.
+    ///
+    /// This follows the [`Layout`](figlet::Layout) of the options, the font's own by default,
+    /// and can fit the banner into a width. With [`Layout::FullWidth`](figlet::Layout::FullWidth)
+    /// and no width, it gives the same banner as [`ascii_art_by_name`](Text::ascii_art_by_name).
+    /// Characters the font has no glyph for are left out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the font or one of the fallback fonts is not available. Use
+    /// [`try_ascii_art_with`](Text::try_ascii_art_with) to handle this instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::prelude::*;
+    /// use overture::primitives::figlet::{FigletOptions, Layout};
+    ///
+    /// let text = primitives::Text::new("Overture", DiscreteCoord::ORIGIN);
+    /// let full = text.ascii_art_with("standard", &FigletOptions::new().with_layout(Layout::FullWidth));
+    /// let smushed = text.ascii_art_with("standard", &FigletOptions::new());
+    /// assert!(smushed[0].dim().x < full[0].dim().x);
+    ///
+    /// // Wrapped onto two banners of 6 rows each to fit in 40 columns
+    /// let wrapped = primitives::Text::new("Hello world", DiscreteCoord::ORIGIN)
+    ///     .ascii_art_with("standard", &FigletOptions::new().with_width(40));
+    /// assert_eq!(wrapped.len(), 12);
+    /// assert!(wrapped.iter().all(|line| line.dim().x <= 40));
+    /// ```
+    pub fn ascii_art_with(&self, font_name: &str, options: &FigletOptions) -> Vec<Text> {
+        self.layout_banner(font_name, options, false)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Converts this `Text` into ASCII art like [`ascii_art_with`](Text::ascii_art_with), but
+    /// fails on unknown fonts and on characters the font has no glyph for.
+    ///
+    /// # Returns
+    ///
+    /// The lines of the banner, [`OvertureError::UnknownFont`] or
+    /// [`OvertureError::InvalidFont`] if the font or a fallback font cannot be loaded, or
+    /// [`OvertureError::UnsupportedCharacter`] naming the font and the first character without
+    /// a glyph. Fallback fonts missing a character are skipped instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::prelude::*;
+    /// use overture::primitives::figlet::FigletOptions;
+    ///
+    /// // Too wide for 20 columns in `big`, but not in `3x5`
+    /// let options = FigletOptions::new().with_width(20).with_fallback_font("3x5");
+    /// let art = primitives::Text::new("12:45", DiscreteCoord::ORIGIN)
+    ///     .try_ascii_art_with("big", &options)
+    ///     .unwrap();
+    /// assert_eq!(art.len(), 6);
+    /// ```
+    pub fn try_ascii_art_with(&self, font_name: &str, options: &FigletOptions) -> OvertureResult<Vec<Text>> {
+        self.layout_banner(font_name, options, true)
+    }
+
+    /// Renders every line of the text as a banner laid out by `options`.
+    ///
+    /// Characters without a glyph in the font asked for are an error if `strict`, and left out
+    /// otherwise.
+    fn layout_banner(&self, font_name: &str, options: &FigletOptions, strict: bool) -> OvertureResult<Vec<Text>> {
+        if self.content.is_empty() {
+            return Ok(vec![]);
+        }
+        let lines: Vec<&str> = self.lines().collect();
+        let missing = |font: &figlet::FigletFont| lines.iter().flat_map(|line| line.chars()).find(|ch| !font.contains(*ch));
+
+        let font = fonts::try_figlet_font(font_name)?;
+        if let Some(ch) = missing(&font).filter(|_| strict) {
+            return Err(OvertureError::UnsupportedCharacter { font: font_name.to_string(), ch });
+        }
+        let mut candidates = vec![font];
+        for name in &options.fallback_fonts {
+            let fallback = fonts::try_figlet_font(name)?;
+            if missing(&fallback).is_none() {
+                candidates.push(fallback);
+            }
+        }
+        let candidates: Vec<&figlet::FigletFont> = candidates.iter().map(|font| font.as_ref()).collect();
+
+        Ok(figlet::render_fitted(&lines, &candidates, options)
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| Text::new(line, DiscreteCoord::new(self.pos.x, self.pos.y + i as u32)))
+            .collect())
     }
 
     /// Converts this `Text` into ASCII art using the default standard FIGfont.
@@ -138,25 +395,51 @@ impl Text {
 }
 
 impl Renderable for Text {
-    /// Converts the text string into a vector of pixels positioned sequentially horizontally.
+    /// Converts the text string into a vector of pixels, one row per line.
+    ///
+    /// The text is split into [graphemes](crate::interfaces::grapheme::graphemes), so that a
+    /// letter with combining accents, a flag or an emoji sequence takes a single pixel. Each one
+    /// starts at `pos + (column, line)`, where `column` is the display width of the line before
+    /// it. A wide grapheme is followed by a continuation pixel.
     ///
-    /// Each character corresponds to one pixel at `pos + (index, 0)`.
+    /// # Examples
+    ///
+    /// ```rust
+    /// use overture::prelude::*;
+    ///
+    /// let pixels = primitives::Text::new("日e\u{301}", DiscreteCoord::ORIGIN).pixels();
+    /// assert_eq!(pixels.len(), 3);
+    /// assert!(pixels[1].content.is_continuation());
+    /// assert_eq!(pixels[2].content.ch, "e\u{301}");
+    /// assert_eq!(pixels[2].position, DiscreteCoord::new(2, 0));
+    ///
+    /// let pixels = primitives::Text::new("ab\nc", DiscreteCoord::new(3, 1)).pixels();
+    /// assert_eq!(pixels[2].position, DiscreteCoord::new(3, 2));
+    /// ```
     fn pixels(&self) -> Vec<Pixel> {
-        let mut pixels = vec![];
-        let mut pointer = self.pos;
-        let unit = DiscreteCoord::new(1, 0);
-        for ch in self.content.chars() {
-            pixels.push(Pixel::new_with_char(ch, pointer, true));
-            pointer += unit;
+        let mut pixels: Vec<Pixel> = vec![];
+        for (row, line) in self.lines().enumerate() {
+            let (cells, _) = self.layout_line(line);
+            for (column, grapheme) in cells {
+                push_grapheme(&mut pixels, grapheme, self.pos + DiscreteCoord::new(column, row as u32));
+            }
         }
         pixels
     }
 
-    /// Returns the dimensions of the text as (width, y-position).
+    /// Returns the dimensions of the text as (width, height).
     ///
-    /// Width is counted as the number of characters in the content.
+    /// Width is the display width of the longest line, in terminal cells, with tabs expanded.
+    /// Height is the number of lines; a trailing line break starts an empty last line.
+    /// An empty text has no size.
     fn dim(&self) -> DiscreteCoord {
-        DiscreteCoord::new(self.content.chars().count() as u32, self.pos.y)
+        if self.content.is_empty() {
+            return DiscreteCoord::ORIGIN;
+        }
+        let (width, height) = self.lines().fold((0, 0), |(width, height), line| {
+            (width.max(self.layout_line(line).1), height + 1)
+        });
+        DiscreteCoord::new(width, height)
     }
 }
 
@@ -169,17 +452,16 @@ impl Deref for Text {
     }
 }
 
-/// Loads a FIGfont by name from the local `fonts/` directory or returns the standard font.
-///
-/// # Panics
-///
-/// Panics if a font file with the given name cannot be found or loaded.
-fn get_font_by_name(name: &str) -> figlet_rs::FIGfont {
-    if name.eq_ignore_ascii_case("standard") {
-        figlet_rs::FIGfont::standard().unwrap()
-    } else {
-        let filename = format!("fonts/{}.flf", name.to_lowercase());
-        figlet_rs::FIGfont::from_file(&filename)
-            .unwrap_or_else(|_| panic!("Failed to load font file: {}", filename))
+/// Appends a protected pixel for `grapheme` at `position`, followed by a continuation pixel
+/// if the grapheme is wide.
+pub(crate) fn push_grapheme(pixels: &mut Vec<Pixel>, grapheme: Grapheme, position: DiscreteCoord) {
+    let wide = grapheme.width() == 2;
+    pixels.push(Pixel::new_with_char(grapheme, position, true));
+    if wide {
+        pixels.push(Pixel::new(
+            RenderChar::continuation(RenderStyle::PLAIN),
+            position + DiscreteCoord::new(1, 0),
+            true,
+        ));
     }
 }
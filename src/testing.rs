@@ -0,0 +1,320 @@
+// Created by Sean L. on Jun. 25.
+// Last Updated by Sean L. on Jun. 25.
+// 
+// overture.rs
+// src/testing.rs
+// 
+// Makabaka1880, 2025. All rights reserved.
+
+//! Headless Testing Utilities
+//!
+//! This module provides [`HeadlessEngine`], an [`OvertureRenderEngine`] that never touches the
+//! terminal, together with assertions on its buffer and golden-file snapshot comparisons.
+//! It is only available with the `testing` cargo feature.
+//!
+//! # Features
+//!
+//! - **Cell assertions:** check the character and style of a single cell.
+//! - **Region assertions:** check that a rectangular area matches multi-line text.
+//! - **Snapshots:** compare whole frames against golden `.txt` files, with a line diff on mismatch.
+//! - **Captured output:** inspect the exact bytes produced by [`OvertureRenderEngine::render`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use overture::prelude::*;
+//! use overture::testing::HeadlessEngine;
+//!
+//! let mut engine = HeadlessEngine::new(6, 3);
+//! let frame = primitives::shape::SoftBox::new(DiscreteCoord::ORIGIN, DiscreteCoord::new(5, 2))
+//!     .rasterize()
+//!     .style(style![ANSISequence::FgCyan]);
+//! engine.load_renderable(frame, None);
+//!
+//! engine.assert_cell(0, 0, '╭', style![ANSISequence::FgCyan]);
+//! engine.assert_region(DiscreteCoord::ORIGIN, "╭────╮\n│    │\n╰────╯");
+//! ```
+//!
+//! # Snapshots
+//!
+//! [`HeadlessEngine::assert_snapshot`] compares the plain-text frame against a golden file.
+//! Trailing whitespace on each line is ignored. If the `OVERTURE_UPDATE_SNAPSHOTS` environment
+//! variable is set, the file is (re)written instead; otherwise a missing file fails the test.
+
+use std::{
+    cell::RefCell,
+    fs,
+    io::{self, Write},
+    ops::{Deref, DerefMut},
+    path::Path,
+    rc::Rc,
+};
+use crate::{
+    engine::OvertureRenderEngine,
+    interfaces::{
+        geometry::DiscreteCoord,
+        grapheme::Grapheme,
+        rendering::{str_width, RenderChar},
+        styling::RenderStyle,
+    },
+};
+
+/// Name of the environment variable that makes [`HeadlessEngine::assert_snapshot`] rewrite golden files.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "OVERTURE_UPDATE_SNAPSHOTS";
+
+/// A writer appending to a shared in-memory byte buffer.
+#[derive(Clone, Default)]
+struct CaptureWriter(Rc<RefCell<Vec<u8>>>);
+
+impl Write for CaptureWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// An [`OvertureRenderEngine`] whose output is captured in memory instead of written to a terminal.
+///
+/// Dereferences to the wrapped engine, so renderables are loaded and rendered as usual.
+/// All `assert_*` methods panic with a readable message on failure, making them suitable
+/// for `#[test]` functions.
+///
+/// # Example
+///
+/// ```rust
+/// use overture::prelude::*;
+/// use overture::testing::HeadlessEngine;
+///
+/// let mut engine = HeadlessEngine::new(10, 1);
+/// engine.load_renderable(primitives::Text::new("Hi", DiscreteCoord::ORIGIN), None);
+/// engine.render(1).unwrap();
+///
+/// assert!(engine.output().contains("Hi"));
+/// engine.assert_char(1, 0, 'i');
+/// ```
+pub struct HeadlessEngine {
+    engine: OvertureRenderEngine,
+    output: CaptureWriter,
+}
+
+impl HeadlessEngine {
+    /// Creates a headless engine with the given width and height.
+    pub fn new(width: u32, height: u32) -> Self {
+        let output = CaptureWriter::default();
+        HeadlessEngine {
+            engine: OvertureRenderEngine::with_writer(output.clone(), width, height),
+            output,
+        }
+    }
+
+    /// Returns everything rendered so far, decoded lossily as UTF-8.
+    pub fn output(&self) -> String {
+        String::from_utf8_lossy(&self.output.0.borrow()).into_owned()
+    }
+
+    /// Returns everything rendered so far and clears the captured output.
+    pub fn take_output(&mut self) -> String {
+        let bytes = std::mem::take(&mut *self.output.0.borrow_mut());
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    /// Returns the cell at `(x, y)`, or `None` if it lies outside the buffer.
+    pub fn cell(&self, x: u32, y: u32) -> Option<&RenderChar> {
+        self.engine.buffer.get(y as usize)?.get(x as usize)
+    }
+
+    /// Asserts that the cell at `(x, y)` holds `ch`, regardless of its style.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is out of bounds or holds a different character or grapheme.
+    pub fn assert_char<G: Into<Grapheme>>(&self, x: u32, y: u32, ch: G) {
+        let ch = ch.into();
+        let cell = self.expect_cell(x, y);
+        assert!(
+            cell.ch == ch,
+            "cell ({}, {}) mismatch\n  expected: {:?}\n  actual:   {:?}",
+            x, y, ch, cell.ch
+        );
+    }
+
+    /// Asserts that the cell at `(x, y)` holds `ch` with exactly the given `style`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is out of bounds or differs in character or style.
+    pub fn assert_cell<G: Into<Grapheme>>(&self, x: u32, y: u32, ch: G, style: RenderStyle) {
+        let ch = ch.into();
+        let cell = self.expect_cell(x, y);
+        assert!(
+            cell.ch == ch && cell.style == style,
+            "cell ({}, {}) mismatch\n  expected: {:?} {:?}\n  actual:   {:?} {:?}",
+            x, y, ch, style, cell.ch, cell.style
+        );
+    }
+
+    /// Asserts that the area starting at `origin` matches the given multi-line text.
+    ///
+    /// Each line of `expected` is compared against as many cells of the corresponding buffer
+    /// row as it is wide in display columns, ignoring styles. A wide character in `expected`
+    /// thus covers both halves of a wide cell.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a line diff if any line differs.
+    pub fn assert_region(&self, origin: DiscreteCoord, expected: &str) {
+        let actual = expected
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let y = origin.y as usize + i;
+                let mut text = String::new();
+                for dx in 0..str_width(line) as usize {
+                    match self.engine.buffer.get(y).and_then(|row| row.get(origin.x as usize + dx)) {
+                        Some(cell) => OvertureRenderEngine::push_plain_cell(&mut text, cell),
+                        None => text.push(' '),
+                    }
+                }
+                text
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if let Some(diff) = diff_lines(expected, &actual) {
+            panic!("region at ({}, {}) mismatch\n{}", origin.x, origin.y, diff);
+        }
+    }
+
+    /// Compares the plain-text frame against the golden file at `path`.
+    ///
+    /// If [`UPDATE_SNAPSHOTS_ENV`] is set, the snapshot is written instead of compared.
+    /// Trailing whitespace on each line is ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a line diff on mismatch, if the golden file is missing and
+    /// [`UPDATE_SNAPSHOTS_ENV`] is not set, or if the golden file cannot be read or written.
+    pub fn assert_snapshot<P: AsRef<Path>>(&self, path: P) {
+        self.check_snapshot(path.as_ref(), std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some());
+    }
+
+    /// Compares the plain-text frame against the golden file at `path`, or writes it there if
+    /// `update`. See [`assert_snapshot`](HeadlessEngine::assert_snapshot).
+    fn check_snapshot(&self, path: &Path, update: bool) {
+        let actual = normalize(&self.engine.to_plain_string());
+
+        if update {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .unwrap_or_else(|e| panic!("failed to create {}: {}", parent.display(), e));
+            }
+            fs::write(path, actual + "\n")
+                .unwrap_or_else(|e| panic!("failed to write snapshot {}: {}", path.display(), e));
+            return;
+        }
+        if !path.exists() {
+            panic!(
+                "snapshot {} is missing (set {} to create it)",
+                path.display(), UPDATE_SNAPSHOTS_ENV
+            );
+        }
+
+        let expected = fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("failed to read snapshot {}: {}", path.display(), e));
+        if let Some(diff) = diff_lines(&normalize(&expected), &actual) {
+            panic!(
+                "snapshot {} mismatch (set {} to update)\n{}",
+                path.display(), UPDATE_SNAPSHOTS_ENV, diff
+            );
+        }
+    }
+
+    fn expect_cell(&self, x: u32, y: u32) -> &RenderChar {
+        self.cell(x, y).unwrap_or_else(|| {
+            panic!(
+                "cell ({}, {}) is outside the {}x{} buffer",
+                x, y, self.engine.width, self.engine.buffer.len()
+            )
+        })
+    }
+}
+
+impl Deref for HeadlessEngine {
+    type Target = OvertureRenderEngine;
+
+    /// Dereferences to the wrapped engine.
+    fn deref(&self) -> &Self::Target {
+        &self.engine
+    }
+}
+
+impl DerefMut for HeadlessEngine {
+    /// Mutably dereferences to the wrapped engine.
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.engine
+    }
+}
+
+/// Strips trailing whitespace from every line and trailing blank lines from the text.
+fn normalize(text: &str) -> String {
+    text.lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+/// Produces a line-by-line diff of two texts, or `None` if they are identical.
+///
+/// Differing lines are shown as a `-` (expected) / `+` (actual) pair, with `|` marking
+/// line boundaries so that whitespace differences stay visible.
+///
+/// # Example
+///
+/// ```rust
+/// use overture::testing::diff_lines;
+///
+/// assert!(diff_lines("a\nb", "a\nb").is_none());
+/// let diff = diff_lines("a\nb", "a\nc").unwrap();
+/// assert!(diff.contains("- |b|") && diff.contains("+ |c|"));
+/// ```
+pub fn diff_lines(expected: &str, actual: &str) -> Option<String> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut diff = String::new();
+
+    for i in 0..expected.len().max(actual.len()) {
+        let (e, a) = (expected.get(i), actual.get(i));
+        if e == a {
+            continue;
+        }
+        diff.push_str(&format!("line {}:\n", i + 1));
+        match e {
+            Some(e) => diff.push_str(&format!("  - |{}|\n", e)),
+            None => diff.push_str("  - <missing>\n"),
+        }
+        match a {
+            Some(a) => diff.push_str(&format!("  + |{}|\n", a)),
+            None => diff.push_str("  + <missing>\n"),
+        }
+    }
+
+    if diff.is_empty() { None } else { Some(diff) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "OVERTURE_UPDATE_SNAPSHOTS")]
+    fn missing_snapshot_fails_instead_of_being_written() {
+        let path = std::env::temp_dir().join(format!("overture_missing_snapshot_{}.txt", std::process::id()));
+        HeadlessEngine::new(4, 1).check_snapshot(&path, false);
+    }
+}
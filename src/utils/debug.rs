@@ -19,7 +19,7 @@ use std::fmt::{Display, Formatter, Result};
 
 impl Display for crate::interfaces::containers::RenderableList {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "[OBJO] RenderableList at {}", &self)
+        write!(f, "[OBJO] RenderableList of {} items", self.len())
     }
 }
 
@@ -0,0 +1,276 @@
+// Created by Sean L. on Jun. 25.
+// Last Updated by Sean L. on Jun. 25.
+// 
+// overture.rs
+// tests/engine.rs
+// 
+// Makabaka1880, 2025. All rights reserved.
+
+//! Headless tests for the output produced by `OvertureRenderEngine`.
+
+use overture::prelude::*;
+use overture::testing::HeadlessEngine;
+
+#[test]
+fn incremental_render_only_emits_changes() {
+    let mut engine = HeadlessEngine::new(10, 2);
+    engine.render(2).unwrap();
+    let full = engine.take_output();
+
+    engine.set_pixel(4, 1, RenderChar::new_plain('X'));
+    engine.render(2).unwrap();
+    let diff = engine.take_output();
+
+    assert!(diff.len() < full.len());
+    assert!(diff.starts_with("\x1b[2;5HX"));
+}
+
+#[test]
+fn identical_styles_share_one_escape_sequence() {
+    let mut engine = HeadlessEngine::new(80, 24);
+    let frame = primitives::shape::SoftBox::new(DiscreteCoord::ORIGIN, DiscreteCoord::new(79, 23))
+        .rasterize()
+        .style(style![ANSISequence::FgCyan]);
+    engine.load_renderable(frame, None);
+    engine.render(24).unwrap();
+    let output = engine.take_output();
+
+    // Cyan is enabled once for the top edge, then once more per side row for its right border,
+    // which stays active through the left border of the next row.
+    assert_eq!(output.matches("\x1b[36m").count(), 1 + 22);
+    assert!(output.ends_with("\x1b[0m"));
+}
+
+#[test]
+fn style_transitions_are_minimal() {
+    let mut engine = HeadlessEngine::new(3, 1);
+    engine.set_pixel(0, 0, RenderChar::new('a', style![ANSISequence::FgCyan]));
+    engine.set_pixel(1, 0, RenderChar::new('b', style![ANSISequence::FgCyan, ANSISequence::Bold]));
+    engine.set_pixel(2, 0, RenderChar::new('c', style![ANSISequence::FgCyan]));
+
+    assert_eq!(engine.to_ansi_string(), "\x1b[36ma\x1b[1mb\x1b[22mc\x1b[0m");
+}
+
+#[test]
+fn layers_composite_by_z_index() {
+    let mut engine = HeadlessEngine::new(6, 1);
+    engine.add_layer("front", 1);
+    engine.load_renderable_on("front", primitives::Text::new("FF", DiscreteCoord::ORIGIN), None);
+    engine.load_renderable(primitives::Text::new("bbbb", DiscreteCoord::ORIGIN), None);
+    engine.assert_region(DiscreteCoord::ORIGIN, "FFbb");
+
+    engine.set_layer_z_index("front", -1);
+    engine.assert_region(DiscreteCoord::ORIGIN, "bbbb");
+
+    engine.set_layer_z_index("front", 2);
+    engine.set_layer_visible("base", false);
+    engine.assert_region(DiscreteCoord::ORIGIN, "FF    ");
+}
+
+#[test]
+fn translucent_layer_tints_content_below() {
+    use overture::interfaces::rendering::Transparency;
+
+    let mut engine = HeadlessEngine::new(6, 1);
+    engine.add_layer("tint", 1);
+    engine.load_renderable_on(
+        "tint",
+        primitives::shape::Fill::new(DiscreteCoord::new(1, 0), DiscreteCoord::new(2, 0))
+            .with_transparency(Transparency::KeepChar)
+            .style(style![ANSISequence::BgBlue]),
+        None,
+    );
+    engine.load_renderable(
+        primitives::Text::new("text", DiscreteCoord::ORIGIN).rasterize().style(style![ANSISequence::FgRed]),
+        None,
+    );
+
+    engine.assert_region(DiscreteCoord::ORIGIN, "text");
+    engine.assert_cell(0, 0, 't', style![ANSISequence::FgRed]);
+    engine.assert_cell(1, 0, 'e', style![ANSISequence::FgRed, ANSISequence::BgBlue]);
+    engine.assert_cell(3, 0, 't', style![ANSISequence::FgRed]);
+
+    // Blank tints keep their background
+    engine.assert_cell(5, 0, ' ', RenderStyle::PLAIN);
+    engine.set_pixel(5, 0, RenderChar::new('!', style![ANSISequence::Bold]).with_transparency(Transparency::KeepStyle));
+    engine.assert_cell(5, 0, '!', RenderStyle::PLAIN);
+}
+
+#[test]
+fn nil_cells_render_as_blanks() {
+    let mut engine = HeadlessEngine::new(3, 1);
+    engine.set_pixel(0, 0, RenderChar::new('a', style![ANSISequence::FgCyan]));
+    engine.set_pixel(1, 0, RenderChar::new('b', RenderStyle::NIL));
+    engine.set_pixel(2, 0, RenderChar::new_plain('c'));
+
+    assert_eq!(engine.to_ansi_string(), "\x1b[36ma\x1b[0m c");
+}
+
+#[test]
+fn hit_test_finds_topmost_owner() {
+    let mut engine = HeadlessEngine::new(12, 3);
+    let panel = engine.load_renderable(
+        primitives::shape::SoftBox::new(DiscreteCoord::ORIGIN, DiscreteCoord::new(11, 2)),
+        None,
+    );
+    engine.add_layer("popup", 1);
+    let label = engine.load_renderable_on("popup", primitives::Text::new("OK", DiscreteCoord::new(5, 1)), None);
+
+    assert_eq!(engine.hit_test(DiscreteCoord::new(0, 0)), Some(panel));
+    assert_eq!(engine.hit_test(DiscreteCoord::new(6, 1)), Some(label));
+    assert_eq!(engine.hit_test(DiscreteCoord::new(3, 1)), None);
+    assert_eq!(engine.layer_of(label).map(|l| l.name.as_str()), Some("popup"));
+
+    // Ownership follows composition
+    engine.set_layer_visible("popup", false);
+    assert_eq!(engine.hit_test(DiscreteCoord::new(6, 1)), None);
+    engine.set_layer_z_index("popup", -1);
+    engine.set_layer_visible("popup", true);
+    assert_eq!(engine.hit_test(DiscreteCoord::new(6, 1)), Some(label));
+    assert_eq!(engine.hit_test(DiscreteCoord::new(11, 2)), Some(panel));
+}
+
+#[test]
+fn layer_mask_does_not_cut_wide_characters() {
+    use overture::engine::BASE_LAYER;
+    use overture::interfaces::layers::rect_mask;
+
+    let mut engine = HeadlessEngine::new(6, 1);
+    let text = engine.load_renderable(primitives::Text::new("漢字", DiscreteCoord::ORIGIN), None);
+    engine.set_layer_mask(BASE_LAYER, Some(rect_mask(DiscreteCoord::ORIGIN, DiscreteCoord::new(2, 0))));
+
+    // `字` straddles the edge of the mask, so it is blanked instead of spilling into column 3
+    assert_eq!(engine.to_plain_string(), "漢    ");
+    assert_eq!(engine.hit_test(DiscreteCoord::new(2, 0)), Some(text));
+    assert_eq!(engine.hit_test(DiscreteCoord::new(3, 0)), None);
+
+    let clipped = primitives::Text::new("漢字", DiscreteCoord::ORIGIN)
+        .clip_to_rect(DiscreteCoord::new(1, 0), DiscreteCoord::new(3, 0));
+    let chars: Vec<String> = clipped.iter().map(|p| p.content.ch.to_string()).collect();
+    assert_eq!(chars, [" ", "字", "\0"]);
+}
+
+#[test]
+fn content_below_the_buffer_grows_it_whether_loaded_or_shown() {
+    let mut loaded = HeadlessEngine::new(4, 1);
+    loaded.load_renderable(primitives::Text::new("ab", DiscreteCoord::new(0, 2)), None);
+    assert_eq!(loaded.to_plain_string(), "    \n    \nab  ");
+
+    let mut shown = HeadlessEngine::new(4, 1);
+    shown.add_layer("hidden", 1);
+    shown.set_layer_visible("hidden", false);
+    let id = shown.load_renderable_on("hidden", primitives::Text::new("ab", DiscreteCoord::new(0, 2)), None);
+    shown.set_layer_visible("hidden", true);
+    assert_eq!(shown.to_plain_string(), loaded.to_plain_string());
+    assert_eq!(shown.hit_test(DiscreteCoord::new(1, 2)), Some(id));
+}
+
+#[test]
+fn resize_shrinks_and_grows_both_axes() {
+    let mut engine = HeadlessEngine::new(6, 3);
+    let frame = engine.load_renderable(
+        primitives::shape::Rectangle::new(DiscreteCoord::ORIGIN, DiscreteCoord::new(5, 2)),
+        None,
+    );
+
+    engine.resize(4, 2);
+    assert_eq!(engine.size(), DiscreteCoord::new(4, 2));
+    assert_eq!(engine.to_plain_string(), "┌───\n│   ");
+
+    engine.resize(8, 4);
+    assert_eq!(engine.size(), DiscreteCoord::new(8, 4));
+    engine.assert_region(DiscreteCoord::ORIGIN, "┌────┐\n│    │\n└────┘");
+    assert_eq!(engine.hit_test(DiscreteCoord::new(5, 2)), Some(frame));
+    assert_eq!(engine.hit_test(DiscreteCoord::new(7, 3)), None);
+
+    // The first frame after a resize is a full repaint
+    engine.render(4).unwrap();
+    assert!(engine.take_output().starts_with("\x1b[1;1H"));
+}
+
+#[test]
+fn scene_objects_update_by_handle() {
+    let mut engine = HeadlessEngine::new(10, 2);
+    let title = engine.load_renderable(primitives::Text::new("Title", DiscreteCoord::ORIGIN), None);
+    let counter = engine.load_renderable(primitives::Text::new("0", DiscreteCoord::new(0, 1)), None);
+    engine.render(2).unwrap();
+    engine.take_output();
+
+    // Only the changed label is redrawn and re-emitted
+    assert!(engine.replace_object(counter, primitives::Text::new("42", DiscreteCoord::new(0, 1))));
+    engine.render(2).unwrap();
+    assert!(engine.take_output().starts_with("\x1b[2;1H42"));
+    engine.assert_region(DiscreteCoord::ORIGIN, "Title\n42");
+
+    assert!(engine.restyle_object(title, style![ANSISequence::Bold]));
+    engine.assert_cell(0, 0, 'T', style![ANSISequence::Bold]);
+    engine.assert_cell(0, 1, '4', RenderStyle::PLAIN);
+
+    assert!(engine.set_object_visible(title, false));
+    engine.assert_region(DiscreteCoord::ORIGIN, "     \n42");
+    assert!(engine.set_object_visible(title, true));
+    engine.assert_region(DiscreteCoord::ORIGIN, "Title\n42");
+
+    assert!(engine.remove_object(counter).is_some());
+    assert!(engine.object(counter).is_none());
+    assert!(!engine.replace_object(counter, primitives::Text::new("gone", DiscreteCoord::ORIGIN)));
+    assert_eq!(engine.to_plain_string(), "Title     \n          ");
+}
+
+#[test]
+fn anchored_objects_follow_resizes() {
+    let mut engine = HeadlessEngine::new(6, 1);
+    engine.load_renderable(primitives::Text::new("ab", DiscreteCoord::ORIGIN), Some(RenderPlacementConfig::TopRight));
+    assert_eq!(engine.to_plain_string(), "    ab");
+
+    engine.resize(4, 1);
+    assert_eq!(engine.to_plain_string(), "  ab");
+}
+
+#[test]
+fn overwriting_half_of_a_wide_character_blanks_the_other() {
+    let mut engine = HeadlessEngine::new(6, 1);
+    engine.set_pixel(0, 0, RenderChar::new_plain('漢'));
+    engine.set_pixel(2, 0, RenderChar::new_plain('字'));
+    engine.assert_region(DiscreteCoord::ORIGIN, "漢字  ");
+
+    engine.set_pixel(1, 0, RenderChar::new_plain('x'));
+    engine.set_pixel(2, 0, RenderChar::new_plain('y'));
+    engine.assert_region(DiscreteCoord::ORIGIN, " xy   ");
+    assert!(!engine.cells().any(|(_, ch)| ch.is_continuation()));
+
+    // A wide character does not fit into the last column
+    engine.set_pixel(5, 0, RenderChar::new_plain('界'));
+    engine.assert_char(5, 0, ' ');
+}
+
+#[test]
+fn wide_characters_are_reprinted_as_a_whole() {
+    let mut engine = HeadlessEngine::new(4, 1);
+    engine.load_renderable(primitives::Text::new("漢a", DiscreteCoord::ORIGIN), None);
+    engine.render(1).unwrap();
+    assert!(engine.take_output().starts_with("\x1b[1;1H漢a "));
+
+    // Restyling only the right half reprints the whole character
+    engine.set_pixel(1, 0, RenderChar::new(' ', style![ANSISequence::FgRed]).with_transparency(
+        overture::interfaces::rendering::Transparency::KeepChar,
+    ));
+    engine.render(1).unwrap();
+    assert_eq!(engine.take_output(), "\x1b[1;1H漢\x1b[2;1H");
+}
+
+#[test]
+fn rows_added_by_a_taller_render_can_be_refreshed() {
+    let mut engine = HeadlessEngine::new(10, 2);
+    engine.render(5).unwrap();
+    engine.add_layer("hidden", 1);
+    engine.set_layer_visible("hidden", false);
+
+    // Refreshing the rows below the original height must not panic
+    let id = engine.load_renderable_on("hidden", primitives::Text::new("x", DiscreteCoord::new(0, 3)), None);
+    assert!(engine.replace_object(id, primitives::Text::new("y", DiscreteCoord::new(0, 3))));
+    engine.set_layer_visible("hidden", true);
+    engine.assert_region(DiscreteCoord::new(0, 3), "y");
+    assert!(engine.remove_object(id).is_some());
+    engine.assert_region(DiscreteCoord::new(0, 3), " ");
+}
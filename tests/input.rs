@@ -0,0 +1,157 @@
+// Created by Sean L. on Jun. 25.
+// Last Updated by Sean L. on Jun. 25.
+// 
+// overture.rs
+// tests/input.rs
+// 
+// Makabaka1880, 2025. All rights reserved.
+
+//! Tests for decoding terminal input bytes into key and mouse events.
+
+use overture::prelude::*;
+use overture::ioopts::input::InputDecoder;
+use overture::testing::HeadlessEngine;
+
+fn keys(bytes: &[u8]) -> Vec<KeyEvent> {
+    let mut decoder = InputDecoder::new();
+    let mut events = decoder.feed(bytes);
+    events.extend(decoder.timeout());
+    events
+        .into_iter()
+        .filter_map(|e| match e {
+            Event::Key(key) => Some(key),
+            _ => None,
+        })
+        .collect()
+}
+
+fn mouse(bytes: &[u8]) -> Vec<MouseEvent> {
+    InputDecoder::new()
+        .feed(bytes)
+        .into_iter()
+        .filter_map(|e| match e {
+            Event::Mouse(mouse) => Some(mouse),
+            _ => None,
+        })
+        .collect()
+}
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::plain(code)
+}
+
+#[test]
+fn arrows_and_navigation_keys() {
+    assert_eq!(
+        keys(b"\x1b[A\x1b[B\x1b[C\x1b[D\x1bOA\x1b[H\x1b[F\x1b[1~\x1b[4~\x1b[5~\x1b[6~\x1b[2~\x1b[3~"),
+        vec![
+            key(KeyCode::Up), key(KeyCode::Down), key(KeyCode::Right), key(KeyCode::Left),
+            key(KeyCode::Up), key(KeyCode::Home), key(KeyCode::End), key(KeyCode::Home),
+            key(KeyCode::End), key(KeyCode::PageUp), key(KeyCode::PageDown),
+            key(KeyCode::Insert), key(KeyCode::Delete),
+        ]
+    );
+}
+
+#[test]
+fn function_keys() {
+    assert_eq!(
+        keys(b"\x1bOP\x1bOS\x1b[15~\x1b[17~\x1b[21~\x1b[24~"),
+        vec![
+            key(KeyCode::F(1)), key(KeyCode::F(4)), key(KeyCode::F(5)),
+            key(KeyCode::F(6)), key(KeyCode::F(10)), key(KeyCode::F(12)),
+        ]
+    );
+}
+
+#[test]
+fn modifiers() {
+    assert_eq!(
+        keys(b"\x1b[1;5C\x1b[1;2P\x1b[5;3~\x01\x1bx\x1b[Z"),
+        vec![
+            KeyEvent::new(KeyCode::Right, KeyModifiers::CTRL),
+            KeyEvent::new(KeyCode::F(1), KeyModifiers::SHIFT),
+            KeyEvent::new(KeyCode::PageUp, KeyModifiers::ALT),
+            KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CTRL),
+            KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT),
+            key(KeyCode::BackTab),
+        ]
+    );
+}
+
+#[test]
+fn control_characters() {
+    assert_eq!(
+        keys(b"\r\t\x7f"),
+        vec![key(KeyCode::Enter), key(KeyCode::Tab), key(KeyCode::Backspace)]
+    );
+}
+
+#[test]
+fn utf8_text_split_across_reads() {
+    let mut decoder = InputDecoder::new();
+    let bytes = "é漢".as_bytes();
+    assert!(decoder.feed(&bytes[..1]).is_empty());
+    assert_eq!(decoder.feed(&bytes[1..3]), vec![Event::Key(key(KeyCode::Char('é')))]);
+    assert_eq!(decoder.feed(&bytes[3..]), vec![Event::Key(key(KeyCode::Char('漢')))]);
+    assert!(!decoder.has_pending());
+}
+
+#[test]
+fn escape_is_only_reported_after_timeout() {
+    let mut decoder = InputDecoder::new();
+    assert!(decoder.feed(b"\x1b").is_empty());
+    assert!(decoder.feed(b"[").is_empty());
+    assert_eq!(decoder.feed(b"A"), vec![Event::Key(key(KeyCode::Up))]);
+
+    assert!(decoder.feed(b"\x1b").is_empty());
+    assert_eq!(decoder.timeout(), vec![Event::Key(key(KeyCode::Esc))]);
+    assert!(!decoder.has_pending());
+}
+
+#[test]
+fn mouse_press_drag_release_and_wheel() {
+    let kinds: Vec<MouseEventKind> = mouse(b"\x1b[<0;3;2M\x1b[<32;4;2M\x1b[<0;4;2m\x1b[<2;1;1M\x1b[<64;1;1M\x1b[<65;1;1M")
+        .into_iter()
+        .map(|m| m.kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            MouseEventKind::Press(MouseButton::Left),
+            MouseEventKind::Drag(MouseButton::Left),
+            MouseEventKind::Release(MouseButton::Left),
+            MouseEventKind::Press(MouseButton::Right),
+            MouseEventKind::ScrollUp,
+            MouseEventKind::ScrollDown,
+        ]
+    );
+}
+
+#[test]
+fn mouse_modifiers_and_split_reports() {
+    let mut decoder = InputDecoder::new();
+    assert!(decoder.feed(b"\x1b[<17;10").is_empty());
+    assert_eq!(
+        decoder.feed(b";5M"),
+        vec![Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Press(MouseButton::Middle),
+            position: DiscreteCoord::new(9, 4),
+            modifiers: KeyModifiers::CTRL,
+        })]
+    );
+}
+
+#[test]
+fn mouse_positions_match_buffer_coordinates() {
+    let mut engine = HeadlessEngine::new(10, 5);
+    engine.render(5).unwrap();
+    engine.take_output();
+
+    let click = mouse(b"\x1b[<0;5;3M")[0];
+    engine.set_pixel(click.position.x, click.position.y, RenderChar::new_plain('X'));
+    engine.render(5).unwrap();
+
+    // The cell drawn at the clicked buffer position is the one the terminal reported
+    assert!(engine.take_output().starts_with("\x1b[3;5HX"));
+}
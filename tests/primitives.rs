@@ -0,0 +1,488 @@
+// Created by Sean L. on Jun. 25.
+// Last Updated by Sean L. on Jun. 25.
+// 
+// overture.rs
+// tests/primitives.rs
+// 
+// Makabaka1880, 2025. All rights reserved.
+
+//! Headless rendering tests for the built-in primitives.
+
+use overture::prelude::*;
+use overture::testing::HeadlessEngine;
+
+#[test]
+fn soft_box_outline() {
+    let mut engine = HeadlessEngine::new(8, 4);
+    let frame = primitives::shape::SoftBox::new(DiscreteCoord::ORIGIN, DiscreteCoord::new(7, 3))
+        .rasterize()
+        .style(style![ANSISequence::FgCyan]);
+    engine.load_renderable(frame, None);
+
+    engine.assert_cell(0, 0, '╭', style![ANSISequence::FgCyan]);
+    engine.assert_cell(7, 0, '╮', style![ANSISequence::FgCyan]);
+    engine.assert_cell(0, 3, '╰', style![ANSISequence::FgCyan]);
+    engine.assert_cell(7, 3, '╯', style![ANSISequence::FgCyan]);
+    engine.assert_region(DiscreteCoord::ORIGIN, "╭──────╮\n│      │\n│      │\n╰──────╯");
+}
+
+#[test]
+fn rectangle_normalizes_corners() {
+    let mut engine = HeadlessEngine::new(5, 3);
+    let rect = primitives::shape::Rectangle::new(DiscreteCoord::new(4, 2), DiscreteCoord::ORIGIN);
+    engine.load_renderable(rect, None);
+
+    engine.assert_region(DiscreteCoord::ORIGIN, "┌───┐\n│   │\n└───┘");
+}
+
+#[test]
+fn text_is_placed_and_styled() {
+    let mut engine = HeadlessEngine::new(11, 3);
+    let label = primitives::Text::new("Hello", DiscreteCoord::ORIGIN)
+        .rasterize()
+        .style(style![ANSISequence::Bold]);
+    engine.load_renderable(label, Some(RenderPlacementConfig::CenterStage));
+
+    engine.assert_region(DiscreteCoord::new(3, 1), "Hello");
+    engine.assert_cell(3, 1, 'H', style![ANSISequence::Bold]);
+    engine.assert_char(2, 1, ' ');
+}
+
+#[test]
+fn ascii_art_snapshot() {
+    let mut engine = HeadlessEngine::new(20, 8);
+    let art = primitives::Text::new("Hi", DiscreteCoord::ORIGIN)
+        .ascii_art_by_name("larry3d")
+        .rasterize()
+        .prune();
+    engine.load_renderable(art, None);
+
+    engine.assert_snapshot("tests/snapshots/ascii_art_larry3d_hi.txt");
+}
+
+#[test]
+fn demo_panel_snapshot() {
+    let mut engine = HeadlessEngine::new(30, 7);
+    let dim = DiscreteCoord::new(30, 7);
+    let frame = primitives::shape::SoftBox::new(DiscreteCoord::ORIGIN, DiscreteCoord::new(29, 6));
+    let title = primitives::Text::new(" Overture ", DiscreteCoord::ORIGIN)
+        .rasterize()
+        .align(RenderPlacementConfig::CenterTop, dim);
+    let body = primitives::Text::new("Low-level rendering", DiscreteCoord::ORIGIN)
+        .rasterize()
+        .align(RenderPlacementConfig::CenterStage, dim);
+    engine.load_renderable(renderable_list![frame, title, body], None);
+
+    engine.assert_snapshot("tests/snapshots/demo_panel.txt");
+}
+
+#[test]
+fn text_clipped_to_soft_box_interior() {
+    let mut engine = HeadlessEngine::new(8, 3);
+    let panel = primitives::shape::SoftBox::new(DiscreteCoord::ORIGIN, DiscreteCoord::new(7, 2));
+    let label = primitives::Text::new("overflowing", DiscreteCoord::new(1, 1))
+        .clip(&panel.footprint().invert());
+    engine.load_renderable(renderable_list![panel, label], None);
+
+    engine.assert_region(DiscreteCoord::ORIGIN, "╭──────╮\n│overfl│\n╰──────╯");
+}
+
+#[test]
+fn wide_characters_take_two_cells() {
+    let mut engine = HeadlessEngine::new(8, 1);
+    engine.load_renderable(primitives::Text::new("a漢字b", DiscreteCoord::ORIGIN), None);
+
+    engine.assert_region(DiscreteCoord::ORIGIN, "a漢字b  ");
+    engine.assert_char(1, 0, '漢');
+    assert!(engine.cell(2, 0).unwrap().is_continuation());
+    engine.assert_char(5, 0, 'b');
+    assert_eq!(engine.to_plain_string(), "a漢字b  ");
+}
+
+#[test]
+fn combining_marks_stay_attached() {
+    let text = primitives::Text::new("e\u{301}\u{323}x", DiscreteCoord::ORIGIN);
+    let pixels = text.pixels();
+
+    assert_eq!(pixels.len(), 2);
+    assert_eq!(pixels[0].content.ch, "e\u{301}\u{323}");
+    assert_eq!(pixels[1].position, DiscreteCoord::new(1, 0));
+    assert_eq!(text.dim().x, 2);
+}
+
+#[test]
+fn grapheme_clusters_fill_single_cells() {
+    let mut engine = HeadlessEngine::new(7, 1);
+    let text = primitives::Text::new("🇯🇵👩‍💻!", DiscreteCoord::ORIGIN);
+    assert_eq!(text.dim().x, 5);
+    engine.load_renderable(text, None);
+
+    engine.assert_char(0, 0, "🇯🇵");
+    engine.assert_char(2, 0, "👩‍💻");
+    engine.assert_char(4, 0, '!');
+    assert_eq!(engine.to_plain_string(), "🇯🇵👩‍💻!  ");
+
+    engine.render(1).unwrap();
+    assert!(engine.take_output().starts_with("\x1b[1;1H🇯🇵👩‍💻!  "));
+}
+
+#[test]
+fn prune_keeps_clusters_and_drops_blanks() {
+    let pixels = primitives::Text::new("a 🇫🇷", DiscreteCoord::ORIGIN)
+        .set_protect(false)
+        .prune();
+
+    assert_eq!(pixels.len(), 3);
+    assert_eq!(pixels[0].content.ch, 'a');
+    assert_eq!(pixels[1].content.ch, "🇫🇷");
+    assert!(pixels[2].content.is_continuation());
+}
+
+#[test]
+fn wide_text_aligns_by_display_width() {
+    let mut engine = HeadlessEngine::new(8, 1);
+    let label = primitives::Text::new("日本", DiscreteCoord::ORIGIN)
+        .align(RenderPlacementConfig::CenterRight, DiscreteCoord::new(8, 1));
+    assert_eq!(label.dim(), DiscreteCoord::new(8, 1));
+    engine.load_renderable(label, None);
+
+    engine.assert_region(DiscreteCoord::ORIGIN, "    日本");
+}
+
+#[test]
+fn multi_line_text_starts_each_line_at_its_x() {
+    let mut engine = HeadlessEngine::new(8, 4);
+    let text = primitives::Text::new("one\r\ntwo\n\nfour", DiscreteCoord::new(2, 0));
+    assert_eq!(text.dim(), DiscreteCoord::new(4, 4));
+    engine.load_renderable(text, None);
+
+    engine.assert_region(DiscreteCoord::ORIGIN, "  one   \n  two   \n        \n  four  ");
+}
+
+#[test]
+fn tabs_expand_to_tab_stops() {
+    let mut engine = HeadlessEngine::new(12, 2);
+    let text = primitives::Text::new("a\tb\nabcd\tc", DiscreteCoord::ORIGIN).with_tab_width(4);
+    assert_eq!(text.dim(), DiscreteCoord::new(9, 2));
+    engine.load_renderable(text, None);
+
+    engine.assert_region(DiscreteCoord::ORIGIN, "a   b\nabcd    c");
+    assert_eq!(primitives::Text::new("\tx", DiscreteCoord::ORIGIN).dim(), DiscreteCoord::new(9, 1));
+    assert_eq!(primitives::Text::new("a\tb", DiscreteCoord::ORIGIN).with_tab_width(0).dim().x, 2);
+}
+
+#[test]
+fn multi_line_text_centers_as_a_block() {
+    let mut engine = HeadlessEngine::new(7, 4);
+    let text = primitives::Text::new("ab\ncdef", DiscreteCoord::ORIGIN);
+    engine.load_renderable(text, Some(RenderPlacementConfig::CenterStage));
+
+    engine.assert_region(DiscreteCoord::new(0, 1), " ab    \n cdef  ");
+}
+
+#[test]
+fn paragraph_wraps_words_and_hyphens() {
+    use overture::primitives::paragraph::Paragraph;
+
+    let paragraph = Paragraph::new("A  well-known   fact\nsupercalifragilistic", DiscreteCoord::ORIGIN, 8);
+    assert_eq!(paragraph.lines(), ["A well-", "known", "fact", "supercal", "ifragili", "stic"]);
+    assert_eq!(paragraph.dim(), DiscreteCoord::new(8, 6));
+}
+
+#[test]
+fn paragraph_measures_display_width() {
+    use overture::primitives::paragraph::{Paragraph, Wrap};
+
+    let words = Paragraph::new("日本語 テキスト", DiscreteCoord::ORIGIN, 7);
+    assert_eq!(words.lines(), ["日本語", "テキス", "ト"]);
+
+    // A wide character never straddles two lines
+    let chars = Paragraph::new("ab日本", DiscreteCoord::ORIGIN, 3).with_wrap(Wrap::Char);
+    assert_eq!(chars.lines(), ["ab", "日", "本"]);
+}
+
+#[test]
+fn paragraph_replaces_control_characters_in_both_modes() {
+    use overture::primitives::paragraph::{Paragraph, Wrap};
+
+    let chars = Paragraph::new("a\tb\x07c", DiscreteCoord::ORIGIN, 10).with_wrap(Wrap::Char);
+    assert_eq!(chars.lines(), ["a b c"]);
+    assert!(chars.pixels().iter().all(|p| !p.content.ch.to_string().contains(char::is_control)));
+
+    let words = Paragraph::new("a\tb\x07c", DiscreteCoord::ORIGIN, 10);
+    assert_eq!(words.lines(), ["a b c"]);
+}
+
+#[test]
+fn paragraph_alignments() {
+    use overture::primitives::paragraph::{Alignment, Paragraph};
+
+    let text = "one two three four five";
+    let lines = |alignment| Paragraph::new(text, DiscreteCoord::ORIGIN, 13).with_alignment(alignment).lines();
+    assert_eq!(lines(Alignment::Left), ["one two three", "four five"]);
+    assert_eq!(lines(Alignment::Right), ["one two three", "    four five"]);
+    assert_eq!(lines(Alignment::Center), ["one two three", "  four five"]);
+
+    // Leftover columns go to the leftmost gaps; the last line is not stretched
+    let justified = Paragraph::new("a b c dddd e", DiscreteCoord::ORIGIN, 8).with_alignment(Alignment::Justify);
+    assert_eq!(justified.lines(), ["a   b  c", "dddd e"]);
+}
+
+#[test]
+fn paragraph_truncates_with_an_ellipsis() {
+    use overture::primitives::paragraph::Paragraph;
+
+    let paragraph = Paragraph::new("lorem ipsum dolor sit amet", DiscreteCoord::ORIGIN, 11).with_max_lines(2);
+    assert_eq!(paragraph.lines(), ["lorem ipsum", "dolor sit…"]);
+
+    let custom = Paragraph::new("lorem ipsum dolor sit amet", DiscreteCoord::ORIGIN, 9)
+        .with_max_lines(1)
+        .with_ellipsis("...");
+    assert_eq!(custom.lines(), ["lorem..."]);
+
+    let fits = Paragraph::new("short", DiscreteCoord::ORIGIN, 9).with_max_lines(1);
+    assert_eq!(fits.lines(), ["short"]);
+}
+
+#[test]
+fn paragraph_pixels_can_be_styled_and_placed() {
+    use overture::primitives::paragraph::{Alignment, Paragraph};
+
+    let mut engine = HeadlessEngine::new(10, 4);
+    let paragraph = Paragraph::new("Hello world", DiscreteCoord::ORIGIN, 6)
+        .with_alignment(Alignment::Center)
+        .rasterize()
+        .style(style![ANSISequence::Bold]);
+    engine.load_renderable(paragraph, Some(RenderPlacementConfig::CenterStage));
+
+    engine.assert_region(DiscreteCoord::new(0, 1), "  Hello   \n  world   ");
+    engine.assert_cell(2, 1, 'H', style![ANSISequence::Bold]);
+}
+
+#[test]
+fn bundled_fonts_are_embedded() {
+    use overture::primitives::fonts::FontRegistry;
+
+    let mut registry = FontRegistry::new();
+    assert_eq!(
+        registry.names(),
+        ["3-d", "3x5", "acrobatic", "alphabet", "big", "blocks", "larry3d", "standard"]
+    );
+    for name in registry.names() {
+        assert!(registry.get(&name).is_some(), "font {} failed to parse", name);
+        assert!(registry.try_get_figlet(&name).is_ok(), "font {} failed to parse for layout", name);
+    }
+    // Parsed fonts are cached
+    let first = registry.get("Larry3D").unwrap();
+    assert!(std::sync::Arc::ptr_eq(&first, &registry.get("larry3d").unwrap()));
+}
+
+#[test]
+fn fonts_can_be_registered_from_files() {
+    use overture::primitives::fonts::FontRegistry;
+
+    let mut registry = FontRegistry::new();
+    let dir = std::env::temp_dir().join(format!("overture-fonts-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("Custom.flf");
+    std::fs::copy("fonts/3x5.flf", &path).unwrap();
+
+    assert_eq!(registry.register_file(&path).unwrap(), "custom");
+    assert!(registry.contains("CUSTOM"));
+    assert!(registry.get("custom").unwrap().convert("ok").is_some());
+    assert!(registry.register_file(dir.join("missing.flf")).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn ascii_art_errors_name_the_font_and_character() {
+    use overture::OvertureError;
+
+    let text = primitives::Text::new("Hi→", DiscreteCoord::ORIGIN);
+    match text.try_ascii_art_by_name("big") {
+        Err(OvertureError::UnsupportedCharacter { font, ch }) => {
+            assert_eq!((font.as_str(), ch), ("big", '→'));
+        }
+        other => panic!("unexpected {:?}", other.map(|lines| lines.len())),
+    }
+    // The lenient variant leaves the character out
+    assert_eq!(text.ascii_art_by_name("big").len(), 8);
+
+    let error = text.try_ascii_art_by_name("bigg").unwrap_err();
+    assert!(matches!(&error, OvertureError::UnknownFont { available, .. } if available.contains(&"big".to_string())));
+    assert!(error.to_string().contains("\"bigg\""));
+}
+
+#[test]
+fn font_file_errors_name_the_path() {
+    use overture::primitives::fonts::FontRegistry;
+    use overture::OvertureError;
+
+    let mut registry = FontRegistry::new();
+    let error = registry.register_file("fonts/missing.flf").unwrap_err();
+    assert!(matches!(error, OvertureError::Io(_)));
+    assert!(error.to_string().contains("fonts/missing.flf"));
+
+    let error = registry.register("broken", "flf2a$ x").unwrap_err();
+    assert!(matches!(&error, OvertureError::InvalidFont { name, .. } if name == "broken"));
+}
+
+#[test]
+fn multi_line_ascii_art_stacks_banners() {
+    let single = primitives::Text::new("A", DiscreteCoord::ORIGIN).try_ascii_art(None).unwrap();
+    let stacked = primitives::Text::new("A\nA", DiscreteCoord::new(2, 1)).try_ascii_art(None).unwrap();
+
+    assert_eq!(stacked.len(), single.len() * 2);
+    assert_eq!(stacked[single.len()].content, single[0].content);
+    assert_eq!(stacked[single.len()].pos, DiscreteCoord::new(2, 1 + single.len() as u32));
+    assert!(primitives::Text::new("", DiscreteCoord::ORIGIN).try_ascii_art(None).unwrap().is_empty());
+}
+
+#[test]
+fn ascii_art_by_name_is_the_full_width_layout() {
+    use overture::primitives::figlet::{FigletOptions, Layout};
+    use overture::primitives::fonts;
+
+    let text = primitives::Text::new("Hi!\nOK", DiscreteCoord::new(1, 2));
+    let contents = |lines: Vec<primitives::Text>| lines.into_iter().map(|line| line.content).collect::<Vec<_>>();
+    let by_name = contents(text.try_ascii_art_by_name("big").unwrap());
+    let full = contents(text.try_ascii_art_with("big", &FigletOptions::new().with_layout(Layout::FullWidth)).unwrap());
+    assert_eq!(by_name, full);
+
+    // The FIGfont converted from the same parse renders the same banner through `figlet_rs`
+    let figfont = fonts::try_font("big").unwrap();
+    assert_eq!(contents(text.try_ascii_art(Some(&figfont)).unwrap()), by_name);
+}
+
+#[test]
+fn figlet_layouts_match_figlet() {
+    use overture::primitives::figlet::{Layout, SmushRules};
+    use overture::primitives::fonts;
+
+    let font = fonts::try_figlet_font("standard").unwrap();
+    assert_eq!(font.default_layout(), Layout::Smushing(SmushRules::from_bits(15)));
+    assert_eq!(
+        font.render("Hi, TY", Layout::FontDefault),
+        [
+            " _   _ _     _______   __",
+            "| | | (_)   |_   _\\ \\ / /",
+            "| |_| | |     | |  \\ V / ",
+            "|  _  | |_    | |   | |  ",
+            "|_| |_|_( )   |_|   |_|  ",
+            "        |/               ",
+        ]
+    );
+    assert_eq!(font.render("Hi", Layout::Fitting)[1], "| | | |(_)");
+    assert_eq!(font.render("Hi", Layout::FullWidth)[1], " | | | | (_)");
+
+    // Glyphs only smush if every row allows it: here `|(` needs the hierarchy rule and `||`
+    // the equal character rule
+    let fitted = font.width("Hi", Layout::Fitting);
+    let both = SmushRules::EQUAL_CHARACTER | SmushRules::HIERARCHY;
+    assert_eq!(font.width("Hi", Layout::Smushing(both)), fitted - 1);
+    assert_eq!(font.width("Hi", Layout::Smushing(SmushRules::HIERARCHY)), fitted);
+    assert_eq!(font.width("Hi", Layout::Smushing(SmushRules::EQUAL_CHARACTER)), fitted);
+}
+
+#[test]
+fn ascii_art_fits_a_width() {
+    use overture::primitives::figlet::FigletOptions;
+
+    let text = primitives::Text::new("one two three", DiscreteCoord::new(1, 2));
+    let unlimited = text.try_ascii_art_with("standard", &FigletOptions::new()).unwrap();
+    assert_eq!(unlimited.len(), 6);
+
+    // Words wrap onto one banner each, words too wide by themselves break
+    let wrapped = text.try_ascii_art_with("standard", &FigletOptions::new().with_width(22)).unwrap();
+    assert_eq!(wrapped.len(), 24);
+    assert!(wrapped.iter().all(|line| line.dim().x <= 22));
+    assert_eq!(wrapped[23].pos, DiscreteCoord::new(1, 25));
+
+    // A narrower fallback font is preferred over wrapping
+    let options = FigletOptions::new().with_width(60).with_fallback_font("3x5");
+    let fallback = text.try_ascii_art_with("standard", &options).unwrap();
+    assert_eq!(fallback.len(), 6);
+    assert!(fallback.iter().all(|line| line.dim().x <= 60));
+
+    let missing = FigletOptions::new().with_fallback_font("tiny");
+    assert!(text.try_ascii_art_with("standard", &missing).is_err());
+}
+
+#[test]
+fn toilet_fonts_render_block_glyphs() {
+    let mut engine = HeadlessEngine::new(12, 6);
+    let art = primitives::Text::new("42", DiscreteCoord::new(1, 0))
+        .try_ascii_art_by_name("blocks")
+        .unwrap();
+    assert_eq!(art.len(), 5);
+    engine.load_renderable(art.rasterize().style(style![ANSISequence::FgGreen]), None);
+
+    engine.assert_region(
+        DiscreteCoord::new(1, 0),
+        "█ █ ███ \n█ █   █ \n███ ███ \n  █ █   \n  █ ███ ",
+    );
+    engine.assert_cell(1, 0, '█', style![ANSISequence::FgGreen]);
+}
+
+#[test]
+fn toilet_fonts_can_be_registered() {
+    use overture::primitives::figlet::Layout;
+    use overture::primitives::fonts::FontRegistry;
+
+    // A two-row font whose only glyphs are a code-tagged arrow and a half-block space
+    let mut tlf = String::from("tlf2a$ 2 2 4 0 1\nTest font\n");
+    for code in (32..=126).chain([196, 214, 220, 228, 246, 252, 223]) {
+        tlf.push_str(if code == 32 { "$$@\n$$@@\n" } else { "@\n@@\n" });
+    }
+    tlf.push_str("0x2192 RIGHTWARDS ARROW\n▀▄ @\n▄▀ @@\n");
+
+    let mut registry = FontRegistry::new();
+    registry.register("arrows", &tlf).unwrap();
+    let font = registry.try_get_figlet("ARROWS").unwrap();
+    assert_eq!(font.default_layout(), Layout::Fitting);
+    assert_eq!(font.render("→ →", Layout::FontDefault), ["▀▄  ▀▄ ", "▄▀  ▄▀ "]);
+    assert_eq!(font.render("→→", Layout::FullWidth), ["▀▄ ▀▄ ", "▄▀ ▄▀ "]);
+    assert_eq!(registry.get("arrows").unwrap().convert("→").unwrap().to_string(), "▀▄ \n▄▀ \n");
+}
+
+#[test]
+fn gradients_follow_the_bounding_box() {
+    use overture::interfaces::gradient::{ColorTarget, Gradient, GradientDirection};
+
+    let red_to_blue = Gradient::new([(255, 0, 0), (0, 0, 255)], GradientDirection::Horizontal);
+    let fg = |r, g, b| style![ANSISequence::FgRGB(r, g, b)];
+
+    // The same colors whether the gradient is applied before or after moving the pixels
+    let art = primitives::Text::new("|", DiscreteCoord::ORIGIN).ascii_art_by_name("blocks").rasterize();
+    let before = art.gradient(&red_to_blue).translate(Translation::new(3, 1));
+    let after = art.translate(Translation::new(3, 1)).gradient(&red_to_blue);
+    let cells = |pixels: &Vec<Pixel>| -> Vec<_> { pixels.iter().map(|p| (p.position, p.content.style)).collect() };
+    assert_eq!(cells(&before), cells(&after));
+    assert_eq!(before[0].content.style, fg(255, 0, 0));
+
+    let mut engine = HeadlessEngine::new(10, 3);
+    let bar = primitives::Text::new("abc\ndef", DiscreteCoord::ORIGIN)
+        .rasterize()
+        .align(RenderPlacementConfig::CenterStage, engine.size())
+        .gradient(&red_to_blue);
+    engine.load_renderable(bar, None);
+    engine.assert_region(DiscreteCoord::new(3, 0), "abc\ndef");
+    engine.assert_cell(3, 0, 'a', fg(255, 0, 0));
+    engine.assert_cell(4, 1, 'e', fg(128, 0, 128));
+    engine.assert_cell(5, 1, 'f', fg(0, 0, 255));
+
+    // Vertical gradients change per row, diagonal ones along both axes
+    let block = primitives::Text::new("ab\ncd\nef", DiscreteCoord::ORIGIN).rasterize();
+    let vertical = block.gradient(&Gradient::new([(0, 0, 0), (0, 200, 0)], GradientDirection::Vertical));
+    assert_eq!(vertical[1].content.style, fg(0, 0, 0));
+    assert_eq!(vertical[2].content.style, fg(0, 100, 0));
+    let diagonal = block.gradient(
+        &Gradient::new([(0, 0, 0), (0, 0, 250)], GradientDirection::Diagonal).with_target(ColorTarget::Background),
+    );
+    assert_eq!(diagonal[1].content.style, style![ANSISequence::BgRGB(0, 0, 50)]);
+    assert_eq!(diagonal[5].content.style, style![ANSISequence::BgRGB(0, 0, 250)]);
+
+    let rainbow = block.gradient(&Gradient::rainbow(GradientDirection::Vertical).with_repeat(2));
+    assert_eq!(rainbow[0].content.style, fg(255, 0, 0));
+    assert_eq!(rainbow[2].content.style, fg(255, 0, 0));
+}